@@ -0,0 +1,181 @@
+//! Known answers, used to verify solutions.
+//!
+//! Answers are stored in a small subset of TOML: one `[[dayNN]]` table per input, with an optional
+//! `input` path (relative to the workspace root, defaulting to the day’s input) and the
+//! `part1` / `part2` answers as strings. A day can appear several times, once per input file.
+//!
+//! ```toml
+//! # comments are allowed
+//! [[day01]]
+//! part1 = "3384232"
+//! part2 = "5073456"
+//!
+//! [[day01]]
+//! input = "day01/other-account.txt"
+//! part1 = "3226488"
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Answers known for a given day and input.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Answers {
+  pub day: u8,
+  /// Input file; `None` stands for the day’s default input.
+  pub input: Option<PathBuf>,
+  pub part_1: Option<String>,
+  pub part_2: Option<String>,
+}
+
+impl Answers {
+  pub fn part(&self, part: u8) -> Option<&str> {
+    match part {
+      1 => self.part_1.as_deref(),
+      2 => self.part_2.as_deref(),
+      _ => None,
+    }
+  }
+}
+
+pub fn load<P>(path: P) -> Result<Vec<Answers>, String>
+where
+  P: AsRef<Path>,
+{
+  let path = path.as_ref();
+  let content = fs::read_to_string(path)
+    .map_err(|e| format!("cannot read answers {}: {}", path.display(), e))?;
+
+  parse(&content).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+pub fn parse(input: &str) -> Result<Vec<Answers>, String> {
+  let mut entries: Vec<Answers> = Vec::new();
+
+  for (i, line) in input.lines().enumerate() {
+    let line_nb = i + 1;
+    let line = line.trim();
+
+    if line.is_empty() || line.starts_with('#') {
+      continue;
+    }
+
+    if line.starts_with("[[") && line.ends_with("]]") {
+      let name = &line[2..line.len() - 2];
+      let day = name
+        .strip_prefix("day")
+        .and_then(|day| day.parse().ok())
+        .ok_or_else(|| format!("line {}: expected [[dayNN]], found {}", line_nb, line))?;
+
+      entries.push(Answers {
+        day,
+        ..Answers::default()
+      });
+
+      continue;
+    }
+
+    let mut kv = line.splitn(2, '=');
+    let key = kv.next().unwrap().trim();
+    let value = kv
+      .next()
+      .ok_or_else(|| format!("line {}: expected key = \"value\"", line_nb))?;
+    let value = parse_string(value.trim()).map_err(|e| format!("line {}: {}", line_nb, e))?;
+
+    let entry = entries
+      .last_mut()
+      .ok_or_else(|| format!("line {}: {} outside of a [[dayNN]] table", line_nb, key))?;
+
+    match key {
+      "input" => entry.input = Some(value.into()),
+      "part1" => entry.part_1 = Some(value),
+      "part2" => entry.part_2 = Some(value),
+      _ => return Err(format!("line {}: unknown key {}", line_nb, key)),
+    }
+  }
+
+  Ok(entries)
+}
+
+/// Parse a basic TOML string, supporting the `\n`, `\t`, `\"` and `\\` escapes.
+fn parse_string(s: &str) -> Result<String, String> {
+  let inner = s
+    .strip_prefix('"')
+    .ok_or_else(|| format!("expected a string: {}", s))?;
+  let mut value = String::new();
+  let mut chars = inner.chars();
+
+  while let Some(c) = chars.next() {
+    match c {
+      '"' => {
+        let rest = chars.as_str().trim();
+
+        if !rest.is_empty() && !rest.starts_with('#') {
+          return Err(format!("unexpected data after string: {}", rest));
+        }
+
+        return Ok(value);
+      }
+
+      '\\' => match chars.next() {
+        Some('n') => value.push('\n'),
+        Some('t') => value.push('\t'),
+        Some('"') => value.push('"'),
+        Some('\\') => value.push('\\'),
+        Some(c) => return Err(format!("unsupported escape: \\{}", c)),
+        None => return Err("unterminated escape".to_owned()),
+      },
+
+      _ => value.push(c),
+    }
+  }
+
+  Err(format!("unterminated string: {}", s))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_answers() {
+    const ANSWERS: &str = r#"
+      # known answers
+      [[day01]]
+      part1 = "3384232"
+      part2 = "5073456" # trailing comment
+
+      [[day08]]
+      input = "day08/input2.txt"
+      part2 = "a\n\"b\"\\"
+    "#;
+
+    assert_eq!(
+      parse(ANSWERS),
+      Ok(vec![
+        Answers {
+          day: 1,
+          input: None,
+          part_1: Some("3384232".to_owned()),
+          part_2: Some("5073456".to_owned()),
+        },
+        Answers {
+          day: 8,
+          input: Some("day08/input2.txt".into()),
+          part_1: None,
+          part_2: Some("a\n\"b\"\\".to_owned()),
+        },
+      ])
+    );
+  }
+
+  #[test]
+  fn parse_errors() {
+    assert!(parse("part1 = \"1\"").is_err());
+    assert!(parse("[[dayx]]").is_err());
+    assert!(parse("[[day01]]\npart3 = \"1\"").is_err());
+    assert!(parse("[[day01]]\npart1 = 1").is_err());
+    assert!(parse("[[day01]]\npart1 = \"1").is_err());
+    assert!(parse("[[day01]]\npart1 = \"1\" 2").is_err());
+  }
+}
@@ -0,0 +1,168 @@
+//! Benchmarking of the solutions: wall time and allocations.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// System allocator counting allocations, so that benchmarks can report them.
+pub struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED_BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+    System.alloc(layout)
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    System.dealloc(ptr, layout)
+  }
+
+  unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED_BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+    System.alloc_zeroed(layout)
+  }
+
+  unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED_BYTES.fetch_add(new_size as u64, Ordering::Relaxed);
+    System.realloc(ptr, layout, new_size)
+  }
+}
+
+fn allocation_counters() -> (u64, u64) {
+  (
+    ALLOCATIONS.load(Ordering::Relaxed),
+    ALLOCATED_BYTES.load(Ordering::Relaxed),
+  )
+}
+
+/// Measurements of a single part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+  pub day: u8,
+  pub part: u8,
+  pub iterations: usize,
+  pub min: Duration,
+  pub median: Duration,
+  pub max: Duration,
+  /// Allocations per iteration.
+  pub allocations: u64,
+  /// Bytes allocated per iteration.
+  pub allocated_bytes: u64,
+}
+
+/// Run `f` once to warm up, then `iterations` times while measuring it.
+pub fn measure<F, R>(day: u8, part: u8, iterations: usize, mut f: F) -> Stats
+where
+  F: FnMut() -> R,
+{
+  assert!(iterations > 0, "at least one iteration is needed");
+
+  drop(f());
+
+  let mut times = Vec::with_capacity(iterations);
+  let (allocations_before, bytes_before) = allocation_counters();
+
+  for _ in 0..iterations {
+    let start = Instant::now();
+    let r = f();
+    times.push(start.elapsed());
+    drop(r);
+  }
+
+  let (allocations_after, bytes_after) = allocation_counters();
+  times.sort();
+
+  Stats {
+    day,
+    part,
+    iterations,
+    min: times[0],
+    median: median(&times),
+    max: times[times.len() - 1],
+    allocations: (allocations_after - allocations_before) / iterations as u64,
+    allocated_bytes: (bytes_after - bytes_before) / iterations as u64,
+  }
+}
+
+/// Median of sorted durations.
+fn median(sorted: &[Duration]) -> Duration {
+  let mid = sorted.len() / 2;
+
+  if sorted.len() % 2 == 1 {
+    sorted[mid]
+  } else {
+    (sorted[mid - 1] + sorted[mid]) / 2
+  }
+}
+
+/// Serialize results as JSON, with durations in nanoseconds.
+pub fn to_json(timestamp: u64, stats: &[Stats]) -> String {
+  let results: Vec<_> = stats
+    .iter()
+    .map(|s| {
+      format!(
+        "    {{\"day\": {}, \"part\": {}, \"iterations\": {}, \"min_ns\": {}, \"median_ns\": {}, \
+         \"max_ns\": {}, \"allocations\": {}, \"allocated_bytes\": {}}}",
+        s.day,
+        s.part,
+        s.iterations,
+        s.min.as_nanos(),
+        s.median.as_nanos(),
+        s.max.as_nanos(),
+        s.allocations,
+        s.allocated_bytes
+      )
+    })
+    .collect();
+
+  format!(
+    "{{\n  \"timestamp\": {},\n  \"results\": [\n{}\n  ]\n}}\n",
+    timestamp,
+    results.join(",\n")
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn median_of_durations() {
+    let ms = Duration::from_millis;
+
+    assert_eq!(median(&[ms(1)]), ms(1));
+    assert_eq!(median(&[ms(1), ms(2), ms(10)]), ms(2));
+    assert_eq!(median(&[ms(1), ms(2), ms(4), ms(10)]), ms(3));
+  }
+
+  #[test]
+  fn json() {
+    let stats = Stats {
+      day: 1,
+      part: 2,
+      iterations: 3,
+      min: Duration::from_nanos(10),
+      median: Duration::from_nanos(20),
+      max: Duration::from_nanos(30),
+      allocations: 4,
+      allocated_bytes: 128,
+    };
+
+    assert_eq!(
+      to_json(42, &[stats]),
+      r#"{
+  "timestamp": 42,
+  "results": [
+    {"day": 1, "part": 2, "iterations": 3, "min_ns": 10, "median_ns": 20, "max_ns": 30, "allocations": 4, "allocated_bytes": 128}
+  ]
+}
+"#
+    );
+  }
+}
@@ -0,0 +1,437 @@
+//! Single entry point running any day’s solution.
+
+mod answers;
+mod bench;
+mod output;
+mod scaffold;
+
+use output::{Format, Record};
+use solution::{Answer, InputSource, Solution};
+use std::env;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::process;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+#[global_allocator]
+static ALLOCATOR: bench::CountingAlloc = bench::CountingAlloc;
+
+const USAGE: &str = "usage:
+  aoc run [<day>] [--part 1|2] [--input <path>] [--format human|json]
+                                                  run all parts of a day, or all days
+  aoc record <day> <path> [--input <path>]        record the intcode session of an interactive day
+  aoc replay <day> <path> [--input <path>]        check an interactive day against a recorded session
+  aoc verify [<day>] [--answers <path>]           check solutions against known answers
+  aoc bench [<day>] [--part 1|2] [--iterations <n>] [--output <path>]
+                                                  benchmark solutions and save results as JSON
+  aoc new <day>                                   create the crate of a day and register it
+
+The input defaults to dayNN/input.txt at the root of the workspace, which is AOC_ROOT if set, or
+else found from the current directory; use --input - to read it from stdin.";
+
+fn solutions() -> Vec<Box<dyn Solution>> {
+  vec![
+    Box::new(day01::Day01),
+    Box::new(day02::Day02),
+    Box::new(day03::Day03),
+    Box::new(day04::Day04),
+    Box::new(day05::Day05),
+    Box::new(day06::Day06),
+    Box::new(day07::Day07),
+    Box::new(day08::Day08),
+    Box::new(day09::Day09),
+    Box::new(day10::Day10),
+    Box::new(day11::Day11),
+    Box::new(day12::Day12),
+    Box::new(day13::Day13),
+  ]
+}
+
+fn parse_day(day: &str) -> Result<u8, String> {
+  day
+    .parse()
+    .map_err(|e| format!("cannot parse day {}: {}", day, e))
+}
+
+fn parse_part(part: &str) -> Result<u8, String> {
+  match part {
+    "1" => Ok(1),
+    "2" => Ok(2),
+    _ => Err(format!("wrong part: {} (expected 1 or 2)", part)),
+  }
+}
+
+/// Split the `--input` option out of the positional arguments.
+fn parse_input(args: &[String]) -> Result<(InputSource, Vec<&String>), String> {
+  let mut source = InputSource::Default;
+  let mut positional = Vec::new();
+  let mut args = args.iter();
+
+  while let Some(arg) = args.next() {
+    if arg == "--input" || arg == "-i" {
+      let path = args.next().ok_or_else(|| "missing input path".to_owned())?;
+      source = InputSource::from_arg(path);
+    } else {
+      positional.push(arg);
+    }
+  }
+
+  Ok((source, positional))
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+  let (source, args) = parse_input(args)?;
+  let mut day = None;
+  let mut parts = vec![1, 2];
+  let mut format = Format::Human;
+  let mut args = args.into_iter();
+
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "--part" | "-p" => {
+        let part = args.next().ok_or_else(|| "missing part".to_owned())?;
+        parts = vec![parse_part(part)?];
+      }
+
+      "--format" | "-f" => {
+        let arg = args.next().ok_or_else(|| "missing format".to_owned())?;
+        format = Format::from_arg(arg)?;
+      }
+
+      _ if day.is_none() => day = Some(parse_day(arg)?),
+
+      _ => return Err(format!("unexpected argument: {}", arg)),
+    }
+  }
+
+  if day.is_none() && source != InputSource::Default {
+    return Err("an input can only be given when running a single day".to_owned());
+  }
+
+  let solutions = solutions();
+  let selected: Vec<_> = solutions
+    .iter()
+    .filter(|solution| day.is_none() || day == Some(solution.day()))
+    .collect();
+
+  if selected.is_empty() {
+    return Err(format!("no solution for day {}", day.unwrap_or(0)));
+  }
+
+  for solution in selected {
+    let input = source.read(solution.day())?;
+
+    for &part in &parts {
+      let start = Instant::now();
+      let answer = solution
+        .part(part, &input)
+        .map_err(|e| format!("day {:02}, part {}: {}", solution.day(), part, e))?;
+      let record = Record {
+        day: solution.day(),
+        part,
+        answer: &answer,
+        duration: start.elapsed(),
+      };
+
+      println!("{}", format.render(&record));
+    }
+  }
+
+  Ok(())
+}
+
+/// Record or replay the session of the interactive intcode days.
+fn session(args: &[String], record: bool) -> Result<(), String> {
+  let (source, args) = parse_input(args)?;
+  let (day, path) = match args.as_slice() {
+    [day, path] => (parse_day(day)?, path),
+    _ => return Err("expected a day and a path".to_owned()),
+  };
+
+  let input = source.read(day)?;
+
+  if record {
+    let start = Instant::now();
+    let answer = match day {
+      11 => day11::record(&input, path)?,
+      13 => day13::record(&input, path)?,
+      _ => return Err(format!("day {} has no interactive session", day)),
+    };
+    let record = Record {
+      day,
+      part: 2,
+      answer: &answer,
+      duration: start.elapsed(),
+    };
+
+    println!("{}", output::human(&record));
+  } else {
+    let summary = match day {
+      11 => day11::replay(&input, path)?,
+      13 => day13::replay(&input, path)?,
+      _ => return Err(format!("day {} has no interactive session", day)),
+    };
+
+    println!("day {:02}: replay OK, {}", day, summary);
+  }
+
+  Ok(())
+}
+
+/// Outcome of checking a single part against its known answer.
+enum Verdict {
+  Pass,
+  Fail(String),
+  Missing,
+}
+
+/// Run a part, catching panics so that a broken day doesn’t prevent checking the others.
+fn timed_part(
+  solution: &dyn Solution,
+  part: u8,
+  input: &str,
+) -> (Result<Answer, String>, Duration) {
+  let start = Instant::now();
+  let answer = panic::catch_unwind(AssertUnwindSafe(|| solution.part(part, input)))
+    .unwrap_or_else(|_| Err("panicked".to_owned()));
+
+  (answer, start.elapsed())
+}
+
+fn verify(args: &[String]) -> Result<(), String> {
+  let mut answers_path = solution::workspace_root().join("answers.toml");
+  let mut day = None;
+  let mut args = args.iter();
+
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "--answers" | "-a" => {
+        let path = args
+          .next()
+          .ok_or_else(|| "missing answers path".to_owned())?;
+        answers_path = PathBuf::from(path);
+      }
+
+      _ if day.is_none() => day = Some(parse_day(arg)?),
+
+      _ => return Err(format!("unexpected argument: {}", arg)),
+    }
+  }
+
+  let known = answers::load(&answers_path)?;
+  let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+  for solution in solutions() {
+    if day.is_some() && day != Some(solution.day()) {
+      continue;
+    }
+
+    let mut entries: Vec<_> = known
+      .iter()
+      .filter(|answers| answers.day == solution.day())
+      .cloned()
+      .collect();
+
+    // days without any known answer are still run to report them as missing
+    if entries.is_empty() {
+      entries.push(answers::Answers {
+        day: solution.day(),
+        ..answers::Answers::default()
+      });
+    }
+
+    for entry in entries {
+      let (source, input_name) = match entry.input {
+        Some(ref path) => (
+          InputSource::Path(solution::workspace_root().join(path)),
+          path.display().to_string(),
+        ),
+        None => (InputSource::Default, "default input".to_owned()),
+      };
+      let input = source.read(solution.day())?;
+
+      for part in 1..=2 {
+        let (answer, duration) = timed_part(solution.as_ref(), part, &input);
+
+        let verdict = match (answer, entry.part(part)) {
+          (Err(e), _) => Verdict::Fail(e),
+          (Ok(_), None) => Verdict::Missing,
+          (Ok(answer), Some(expected)) => {
+            let answer = answer.to_string();
+
+            if answer == expected {
+              Verdict::Pass
+            } else {
+              Verdict::Fail(format!("expected {:?}, got {:?}", expected, answer))
+            }
+          }
+        };
+
+        let status = match verdict {
+          Verdict::Pass => {
+            passed += 1;
+            "pass".to_owned()
+          }
+
+          Verdict::Fail(reason) => {
+            failed += 1;
+            format!("FAIL: {}", reason)
+          }
+
+          Verdict::Missing => {
+            missing += 1;
+            "missing".to_owned()
+          }
+        };
+
+        println!(
+          "day {:02}, part {} ({}): {} [{:?}]",
+          solution.day(),
+          part,
+          input_name,
+          status,
+          duration
+        );
+      }
+    }
+  }
+
+  println!(
+    "\n{} passed, {} failed, {} missing",
+    passed, failed, missing
+  );
+
+  if failed > 0 {
+    Err(format!("{} answer(s) do not match", failed))
+  } else {
+    Ok(())
+  }
+}
+
+fn bench(args: &[String]) -> Result<(), String> {
+  let mut day = None;
+  let mut parts = vec![1, 2];
+  let mut iterations = 10;
+  let mut output = solution::workspace_root().join("target").join("bench.json");
+  let mut args = args.iter();
+
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "--part" | "-p" => {
+        let part = args.next().ok_or_else(|| "missing part".to_owned())?;
+        parts = vec![parse_part(part)?];
+      }
+
+      "--iterations" | "-n" => {
+        let n = args.next().ok_or_else(|| "missing iterations".to_owned())?;
+        iterations = n
+          .parse()
+          .ok()
+          .filter(|&n| n > 0)
+          .ok_or_else(|| format!("wrong number of iterations: {}", n))?;
+      }
+
+      "--output" | "-o" => {
+        let path = args
+          .next()
+          .ok_or_else(|| "missing output path".to_owned())?;
+        output = PathBuf::from(path);
+      }
+
+      _ if day.is_none() => day = Some(parse_day(arg)?),
+
+      _ => return Err(format!("unexpected argument: {}", arg)),
+    }
+  }
+
+  if cfg!(debug_assertions) {
+    eprintln!("warning: benchmarking a debug build; use cargo run --release");
+  }
+
+  let mut results = Vec::new();
+
+  println!(
+    "{:>3} {:>4} {:>12} {:>12} {:>12} {:>10} {:>12}",
+    "day", "part", "min", "median", "max", "allocs", "bytes"
+  );
+
+  for solution in solutions() {
+    if day.is_some() && day != Some(solution.day()) {
+      continue;
+    }
+
+    let input = InputSource::Default.read(solution.day())?;
+
+    for &part in &parts {
+      // don’t measure a solution that fails
+      solution
+        .part(part, &input)
+        .map_err(|e| format!("day {:02}, part {}: {}", solution.day(), part, e))?;
+
+      let stats = bench::measure(solution.day(), part, iterations, || {
+        solution.part(part, &input)
+      });
+
+      println!(
+        "{:>3} {:>4} {:>12} {:>12} {:>12} {:>10} {:>12}",
+        format!("{:02}", stats.day),
+        stats.part,
+        format!("{:.2?}", stats.min),
+        format!("{:.2?}", stats.median),
+        format!("{:.2?}", stats.max),
+        stats.allocations,
+        stats.allocated_bytes
+      );
+
+      results.push(stats);
+    }
+  }
+
+  let timestamp = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_secs())
+    .unwrap_or(0);
+
+  if let Some(dir) = output.parent() {
+    fs::create_dir_all(dir).map_err(|e| format!("cannot create {}: {}", dir.display(), e))?;
+  }
+
+  fs::write(&output, bench::to_json(timestamp, &results))
+    .map_err(|e| format!("cannot write {}: {}", output.display(), e))?;
+  println!("\nresults saved to {}", output.display());
+
+  Ok(())
+}
+
+fn new(args: &[String]) -> Result<(), String> {
+  let day = match args {
+    [day] => parse_day(day)?,
+    _ => return Err("expected a single day".to_owned()),
+  };
+  let root = solution::workspace_root();
+
+  scaffold::new_day(&root, day)?;
+  println!("created {}", root.join(format!("day{:02}", day)).display());
+
+  Ok(())
+}
+
+fn main() {
+  let args: Vec<_> = env::args().skip(1).collect();
+
+  let result = match args.split_first() {
+    Some((command, args)) if command == "run" => run(args),
+    Some((command, args)) if command == "record" => session(args, true),
+    Some((command, args)) if command == "replay" => session(args, false),
+    Some((command, args)) if command == "verify" => verify(args),
+    Some((command, args)) if command == "bench" => bench(args),
+    Some((command, args)) if command == "new" => new(args),
+    _ => Err("missing or unknown command".to_owned()),
+  };
+
+  if let Err(e) = result {
+    eprintln!("error: {}\n\n{}", e, USAGE);
+    process::exit(1);
+  }
+}
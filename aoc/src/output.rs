@@ -0,0 +1,130 @@
+//! Rendering of answers, for humans or for other programs.
+
+use solution::Answer;
+use std::time::Duration;
+
+/// Output format of the answers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+  /// One line per answer, images on the following lines.
+  Human,
+  /// One JSON object per line, with the duration in seconds.
+  Json,
+}
+
+impl Format {
+  pub fn from_arg(arg: &str) -> Result<Self, String> {
+    match arg {
+      "human" => Ok(Format::Human),
+      "json" => Ok(Format::Json),
+      _ => Err(format!("unknown format: {} (expected human or json)", arg)),
+    }
+  }
+
+  pub fn render(self, record: &Record) -> String {
+    match self {
+      Format::Human => human(record),
+      Format::Json => json(record),
+    }
+  }
+}
+
+/// An answer computed by a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record<'a> {
+  pub day: u8,
+  pub part: u8,
+  pub answer: &'a Answer,
+  pub duration: Duration,
+}
+
+pub fn human(record: &Record) -> String {
+  if record.answer.is_multiline() {
+    format!(
+      "day {:02}, part {}:\n{}",
+      record.day, record.part, record.answer
+    )
+  } else {
+    format!(
+      "day {:02}, part {}: {}",
+      record.day, record.part, record.answer
+    )
+  }
+}
+
+pub fn json(record: &Record) -> String {
+  let answer = match record.answer {
+    Answer::Number(n) => n.to_string(),
+    Answer::Large(n) => n.to_string(),
+    Answer::Text(text) | Answer::Image(text) => json_string(text),
+    Answer::NotFound => "null".to_owned(),
+  };
+
+  format!(
+    "{{\"day\": {}, \"part\": {}, \"answer\": {}, \"duration\": {}}}",
+    record.day,
+    record.part,
+    answer,
+    record.duration.as_secs_f64()
+  )
+}
+
+/// Quote and escape a string as JSON.
+pub fn json_string(s: &str) -> String {
+  let mut quoted = String::with_capacity(s.len() + 2);
+  quoted.push('"');
+
+  for c in s.chars() {
+    match c {
+      '"' => quoted.push_str("\\\""),
+      '\\' => quoted.push_str("\\\\"),
+      '\n' => quoted.push_str("\\n"),
+      '\r' => quoted.push_str("\\r"),
+      '\t' => quoted.push_str("\\t"),
+      c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+      c => quoted.push(c),
+    }
+  }
+
+  quoted.push('"');
+  quoted
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn record(answer: &Answer) -> Record<'_> {
+    Record {
+      day: 8,
+      part: 2,
+      answer,
+      duration: Duration::from_millis(1500),
+    }
+  }
+
+  #[test]
+  fn human_output() {
+    assert_eq!(human(&record(&Answer::Number(42))), "day 08, part 2: 42");
+    assert_eq!(
+      human(&record(&Answer::Image("#.\n.#\n".to_owned()))),
+      "day 08, part 2:\n#.\n.#"
+    );
+  }
+
+  #[test]
+  fn json_output() {
+    assert_eq!(
+      json(&record(&Answer::Number(42))),
+      r#"{"day": 8, "part": 2, "answer": 42, "duration": 1.5}"#
+    );
+    assert_eq!(
+      json(&record(&Answer::Image("█ \"\n\u{1}".to_owned()))),
+      r#"{"day": 8, "part": 2, "answer": "█ \"\n\u0001", "duration": 1.5}"#
+    );
+    assert_eq!(
+      json(&record(&Answer::NotFound)),
+      r#"{"day": 8, "part": 2, "answer": null, "duration": 1.5}"#
+    );
+  }
+}
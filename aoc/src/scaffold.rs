@@ -0,0 +1,182 @@
+//! Generation of the crate of a new day, registered in the workspace and in the runner.
+
+use std::fs;
+use std::path::Path;
+
+const CARGO_TEMPLATE: &str = include_str!("../templates/Cargo.toml.in");
+const LIB_TEMPLATE: &str = include_str!("../templates/lib.rs.in");
+
+/// Fill a template in for the given day.
+fn render(template: &str, day: u8) -> String {
+  template
+    .replace("{dd}", &format!("{:02}", day))
+    .replace("{day}", &day.to_string())
+}
+
+/// Insert `line` in the sorted block of lines following the line equal to `after`; the block ends
+/// at the line equal to `end`, or at the end of the content.
+fn insert_sorted(
+  content: &str,
+  after: &str,
+  end: Option<&str>,
+  line: &str,
+) -> Result<String, String> {
+  let lines: Vec<_> = content.lines().collect();
+  let start = lines
+    .iter()
+    .position(|l| *l == after)
+    .ok_or_else(|| format!("cannot find {:?}", after))?
+    + 1;
+  let stop = match end {
+    Some(end) => lines[start..]
+      .iter()
+      .position(|l| *l == end)
+      .map(|i| start + i)
+      .ok_or_else(|| format!("cannot find {:?} after {:?}", end, after))?,
+    None => lines.len(),
+  };
+
+  if lines[start..stop].contains(&line) {
+    return Err(format!("{} is already registered", line.trim()));
+  }
+
+  let at = lines[start..stop]
+    .iter()
+    .position(|l| *l > line)
+    .map_or(stop, |i| start + i);
+
+  let mut output = lines[..at].to_vec();
+  output.push(line);
+  output.extend_from_slice(&lines[at..]);
+
+  Ok(output.join("\n") + "\n")
+}
+
+/// Add a crate to the members of the workspace manifest.
+fn add_member(manifest: &str, name: &str) -> Result<String, String> {
+  insert_sorted(
+    manifest,
+    "members = [",
+    Some("]"),
+    &format!("  \"{}\",", name),
+  )
+}
+
+/// Add a path dependency to the manifest of the runner, whose dependencies come last.
+fn add_dependency(manifest: &str, name: &str) -> Result<String, String> {
+  let line = format!("{} = {{ path = \"../{}\" }}", name, name);
+  insert_sorted(manifest, "[dependencies]", None, &line)
+}
+
+/// Add a day to the solutions of the runner.
+fn register(main: &str, name: &str) -> Result<String, String> {
+  let start = main
+    .find("fn solutions() -> Vec<Box<dyn Solution>> {")
+    .ok_or_else(|| "cannot find the solutions".to_owned())?;
+  let line = format!("    Box::new({}::Day{}),", name, &name[3..]);
+  let solutions = insert_sorted(&main[start..], "  vec![", Some("  ]"), &line)?;
+
+  Ok(format!("{}{}", &main[..start], solutions))
+}
+
+/// Edit of the content of a file registering a crate.
+type Edit = fn(&str, &str) -> Result<String, String>;
+
+fn create(path: &Path, content: &str) -> Result<(), String> {
+  fs::write(path, content).map_err(|e| format!("cannot write {}: {}", path.display(), e))
+}
+
+/// Create the crate of a day in the workspace at `root` and register it.
+pub fn new_day(root: &Path, day: u8) -> Result<(), String> {
+  if !(1..=25).contains(&day) {
+    return Err(format!("there is no day {}", day));
+  }
+
+  let name = format!("day{:02}", day);
+  let dir = root.join(&name);
+
+  if dir.exists() {
+    return Err(format!("{} already exists", dir.display()));
+  }
+
+  let workspace = root.join("Cargo.toml");
+  let runner_manifest = root.join("aoc").join("Cargo.toml");
+  let runner = root.join("aoc").join("src").join("main.rs");
+  let edits: [(&Path, Edit); 3] = [
+    (&workspace, add_member),
+    (&runner_manifest, add_dependency),
+    (&runner, register),
+  ];
+
+  // edit everything in memory first, so that nothing is written if any file cannot be updated
+  let mut updates = Vec::new();
+  for (path, edit) in edits.iter() {
+    let content =
+      fs::read_to_string(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+    let content = edit(&content, &name).map_err(|e| format!("{}: {}", path.display(), e))?;
+    updates.push((path, content));
+  }
+
+  fs::create_dir_all(dir.join("src"))
+    .map_err(|e| format!("cannot create {}: {}", dir.display(), e))?;
+  create(&dir.join("Cargo.toml"), &render(CARGO_TEMPLATE, day))?;
+  create(&dir.join("src").join("lib.rs"), &render(LIB_TEMPLATE, day))?;
+  create(&dir.join("input.txt"), "")?;
+
+  for (path, content) in updates {
+    create(path, &content)?;
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn templates() {
+    let lib = render(LIB_TEMPLATE, 7);
+
+    assert!(lib.contains("pub struct Day07;"));
+    assert!(lib.contains("    7\n"));
+    assert!(!lib.contains("{dd}"));
+    assert!(render(CARGO_TEMPLATE, 7).contains("name = \"day07\""));
+  }
+
+  #[test]
+  fn members() {
+    let manifest = "[workspace]\nmembers = [\n  \"aoc\",\n  \"day01\",\n  \"grid\",\n]\n";
+
+    assert_eq!(
+      add_member(manifest, "day02"),
+      Ok(
+        "[workspace]\nmembers = [\n  \"aoc\",\n  \"day01\",\n  \"day02\",\n  \"grid\",\n]\n"
+          .to_owned()
+      )
+    );
+    assert!(add_member(manifest, "day01").is_err());
+    assert!(add_member("[workspace]\n", "day02").is_err());
+  }
+
+  #[test]
+  fn dependencies() {
+    let manifest = "[package]\nname = \"aoc\"\n\n[dependencies]\nday01 = { path = \"../day01\" }\nsolution = { path = \"../solution\" }\n";
+
+    assert_eq!(
+      add_dependency(manifest, "day02"),
+      Ok("[package]\nname = \"aoc\"\n\n[dependencies]\nday01 = { path = \"../day01\" }\nday02 = { path = \"../day02\" }\nsolution = { path = \"../solution\" }\n".to_owned())
+    );
+  }
+
+  #[test]
+  fn solutions() {
+    let main =
+      "fn solutions() -> Vec<Box<dyn Solution>> {\n  vec![\n    Box::new(day01::Day01),\n  ]\n}\n";
+
+    assert_eq!(
+      register(main, "day14"),
+      Ok("fn solutions() -> Vec<Box<dyn Solution>> {\n  vec![\n    Box::new(day01::Day01),\n    Box::new(day14::Day14),\n  ]\n}\n".to_owned())
+    );
+  }
+}
@@ -0,0 +1,364 @@
+//! Fuel required by modules, with a per-module breakdown to audit the totals.
+
+use std::collections::HashMap;
+
+/// How the division of a mass is rounded.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Rounding {
+  Down,
+  Up,
+  /// Halves are rounded up.
+  Nearest,
+}
+
+/// Formula giving the fuel required to launch a mass: `round(mass / divisor) - subtraction`, masses
+/// too light to need fuel requiring none.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FuelModel {
+  divisor: u64,
+  subtraction: u64,
+  rounding: Rounding,
+}
+
+impl Default for FuelModel {
+  fn default() -> Self {
+    FuelModel::PUZZLE
+  }
+}
+
+impl FuelModel {
+  /// The formula of the puzzle, `mass / 3 - 2`.
+  pub const PUZZLE: FuelModel = FuelModel {
+    divisor: 3,
+    subtraction: 2,
+    rounding: Rounding::Down,
+  };
+
+  /// A formula; it must require less fuel than the launched mass, so that fuel eventually requires
+  /// no more fuel.
+  pub fn new(divisor: u64, subtraction: u64, rounding: Rounding) -> Result<Self, String> {
+    let model = FuelModel {
+      divisor,
+      subtraction,
+      rounding,
+    };
+
+    // with a divisor of at least 2, fuel(m) < m for every m as soon as it holds for 1
+    if divisor < 2 || model.fuel(1) > 0 {
+      return Err(format!(
+        "fuel would never run out with {:?} rounding of mass / {} - {}",
+        rounding, divisor, subtraction
+      ));
+    }
+
+    Ok(model)
+  }
+
+  /// Fuel required to launch a given mass.
+  pub fn fuel(&self, mass: u64) -> u64 {
+    let (quotient, remainder) = (mass / self.divisor, mass % self.divisor);
+    let rounded = match self.rounding {
+      Rounding::Down => quotient,
+      Rounding::Up if remainder > 0 => quotient + 1,
+      Rounding::Nearest if remainder >= self.divisor - remainder => quotient + 1,
+      _ => quotient,
+    };
+
+    rounded.saturating_sub(self.subtraction)
+  }
+
+  /// Fuel of each stage: the fuel for `mass`, then the fuel for that fuel, until none is needed.
+  pub fn stages(self, mass: u64) -> impl Iterator<Item = u64> {
+    let mut fuel = self.fuel(mass);
+
+    std::iter::from_fn(move || {
+      if fuel == 0 {
+        None
+      } else {
+        let stage = fuel;
+        fuel = self.fuel(fuel);
+        Some(stage)
+      }
+    })
+  }
+
+  /// Fuel required to launch a mass and its fuel: the base fuel of the mass plus every stage.
+  pub fn total_fuel(&self, mass: u64) -> u64 {
+    self.stages(mass).sum()
+  }
+}
+
+/// Evaluator of the total fuel of many masses, remembering the fuel of the stages it went through.
+///
+/// Stages quickly converge to the same small fuels, so that only the first few stages of a new mass
+/// are computed.
+#[derive(Clone, Debug)]
+pub struct Memo {
+  model: FuelModel,
+  totals: HashMap<u64, u64>,
+}
+
+impl Memo {
+  pub fn new(model: FuelModel) -> Self {
+    Memo {
+      model,
+      totals: HashMap::new(),
+    }
+  }
+
+  /// Fuel required to launch a mass and its fuel.
+  pub fn total_fuel(&mut self, mass: u64) -> u64 {
+    if let Some(&total) = self.totals.get(&mass) {
+      return total;
+    }
+
+    // the mass and its stages, up to the first one whose total is known or the last one
+    let mut chain = vec![mass];
+    let mut total = 0;
+
+    for stage in self.model.stages(mass) {
+      chain.push(stage);
+
+      if let Some(&known) = self.totals.get(&stage) {
+        total = known;
+        break;
+      }
+    }
+
+    // the total of a mass is its fuel, the next stage, plus the total of that stage
+    for pair in chain.windows(2).rev() {
+      total += pair[1];
+      self.totals.insert(pair[0], total);
+    }
+
+    total
+  }
+}
+
+/// Fuel required by a single module.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Breakdown {
+  /// Line of the module in the input.
+  pub line: usize,
+  pub mass: u64,
+  /// Fuel of each stage, starting with the base fuel of the module.
+  pub stages: Vec<u64>,
+}
+
+impl Breakdown {
+  pub fn new(model: &FuelModel, line: usize, mass: u64) -> Self {
+    Breakdown {
+      line,
+      mass,
+      stages: model.stages(mass).collect(),
+    }
+  }
+
+  /// Fuel for the mass of the module only.
+  pub fn base(&self) -> u64 {
+    self.stages.first().cloned().unwrap_or(0)
+  }
+
+  /// Fuel for the fuel of the module.
+  pub fn extra(&self) -> u64 {
+    self.stages.iter().skip(1).sum()
+  }
+
+  pub fn total(&self) -> u64 {
+    self.stages.iter().sum()
+  }
+}
+
+/// Fuel breakdown of every module of an input.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Report {
+  modules: Vec<Breakdown>,
+}
+
+impl Report {
+  /// Read the masses of the modules, one per line.
+  pub fn parse(input: &str, model: &FuelModel) -> parse::Result<Self> {
+    let modules = parse::records(input, |line| {
+      Ok(Breakdown::new(
+        model,
+        line.number(),
+        line.parse(line.text())?,
+      ))
+    })?;
+
+    Ok(Report { modules })
+  }
+
+  /// Modules, in input order.
+  pub fn modules(&self) -> &[Breakdown] {
+    &self.modules
+  }
+
+  /// Modules, from the one requiring the most fuel to the one requiring the least.
+  pub fn dominant(&self) -> Vec<&Breakdown> {
+    let mut modules: Vec<_> = self.modules.iter().collect();
+    modules.sort_by(|a, b| b.total().cmp(&a.total()).then(a.line.cmp(&b.line)));
+    modules
+  }
+
+  /// Fuel for the masses of the modules only.
+  pub fn base_fuel(&self) -> Result<u64, String> {
+    checked_sum(self.modules.iter().map(Breakdown::base))
+  }
+
+  /// Fuel for the masses of the modules and their fuel.
+  pub fn total_fuel(&self) -> Result<u64, String> {
+    checked_sum(self.modules.iter().map(Breakdown::total))
+  }
+
+  /// One row per module, stages being separated by semicolons.
+  pub fn csv(&self) -> String {
+    let mut output = "line,mass,base_fuel,extra_fuel,total_fuel,stages\n".to_owned();
+
+    for module in &self.modules {
+      output.push_str(&format!(
+        "{},{},{},{},{},{}\n",
+        module.line,
+        module.mass,
+        module.base(),
+        module.extra(),
+        module.total(),
+        join(&module.stages, ";")
+      ));
+    }
+
+    output
+  }
+
+  /// JSON array with one object per module.
+  pub fn json(&self) -> String {
+    let modules: Vec<_> = self
+      .modules
+      .iter()
+      .map(|module| {
+        format!(
+          "  {{\"line\": {}, \"mass\": {}, \"base_fuel\": {}, \"extra_fuel\": {}, \"total_fuel\": \
+           {}, \"stages\": [{}]}}",
+          module.line,
+          module.mass,
+          module.base(),
+          module.extra(),
+          module.total(),
+          join(&module.stages, ", ")
+        )
+      })
+      .collect();
+
+    if modules.is_empty() {
+      "[]\n".to_owned()
+    } else {
+      format!("[\n{}\n]\n", modules.join(",\n"))
+    }
+  }
+}
+
+fn checked_sum<I>(fuels: I) -> Result<u64, String>
+where
+  I: IntoIterator<Item = u64>,
+{
+  fuels.into_iter().try_fold(0u64, |total, fuel| {
+    total
+      .checked_add(fuel)
+      .ok_or_else(|| "the total fuel overflows".to_owned())
+  })
+}
+
+fn join(stages: &[u64], sep: &str) -> String {
+  stages
+    .iter()
+    .map(u64::to_string)
+    .collect::<Vec<_>>()
+    .join(sep)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn puzzle_model() {
+    let model = FuelModel::default();
+
+    assert_eq!(model.fuel(12), 2);
+    assert_eq!(model.fuel(14), 2);
+    assert_eq!(model.fuel(1969), 654);
+    assert_eq!(model.fuel(100756), 33583);
+    assert_eq!(model.fuel(5), 0);
+    assert_eq!(model.total_fuel(1969) - model.fuel(1969), 312);
+    assert_eq!(model.total_fuel(14), 2);
+    assert_eq!(model.total_fuel(1969), 966);
+    assert_eq!(model.total_fuel(100756), 50346);
+    assert_eq!(
+      Breakdown::new(&model, 1, 1969).stages,
+      vec![654, 216, 70, 21, 5]
+    );
+  }
+
+  #[test]
+  fn models() {
+    let up = FuelModel::new(3, 2, Rounding::Up).unwrap();
+    assert_eq!(up.fuel(14), 3);
+    assert_eq!(up.fuel(12), 2);
+
+    let nearest = FuelModel::new(4, 1, Rounding::Nearest).unwrap();
+    assert_eq!(nearest.fuel(13), 2);
+    assert_eq!(nearest.fuel(14), 3);
+    assert_eq!(nearest.stages(100).collect::<Vec<_>>(), vec![24, 5]);
+
+    assert!(FuelModel::new(1, 2, Rounding::Down).is_err());
+    assert!(FuelModel::new(3, 0, Rounding::Up).is_err());
+    assert!(FuelModel::new(2, 0, Rounding::Nearest).is_err());
+    assert!(FuelModel::new(3, 0, Rounding::Nearest).is_ok());
+  }
+
+  #[test]
+  fn memo() {
+    let model = FuelModel::default();
+    let mut memo = Memo::new(model);
+
+    for &mass in &[14, 1969, 100756, 1969, 0, u64::MAX, u64::MAX / 7] {
+      assert_eq!(memo.total_fuel(mass), model.total_fuel(mass));
+    }
+
+    let model = FuelModel::new(2, 0, Rounding::Down).unwrap();
+    let mut memo = Memo::new(model);
+    assert_eq!(memo.total_fuel(u64::MAX), u64::MAX - 64);
+  }
+
+  #[test]
+  fn report() {
+    let report = Report::parse("14\n1969\n\n100756\n", &FuelModel::PUZZLE).unwrap();
+
+    assert_eq!(report.base_fuel(), Ok(2 + 654 + 33583));
+    assert_eq!(report.total_fuel(), Ok(2 + 966 + 50346));
+    assert_eq!(
+      report.dominant().iter().map(|m| m.line).collect::<Vec<_>>(),
+      vec![4, 2, 1]
+    );
+
+    let csv = report.csv();
+    let mut rows = csv.lines().skip(1);
+    assert_eq!(rows.next(), Some("1,14,2,0,2,2"));
+    assert_eq!(rows.next(), Some("2,1969,654,312,966,654;216;70;21;5"));
+
+    assert!(report.json().starts_with(
+      "[\n  {\"line\": 1, \"mass\": 14, \"base_fuel\": 2, \"extra_fuel\": 0, \"total_fuel\": 2, \
+       \"stages\": [2]},\n"
+    ));
+    assert_eq!(
+      Report::parse("", &FuelModel::PUZZLE).unwrap().json(),
+      "[]\n"
+    );
+  }
+
+  #[test]
+  fn overflow() {
+    assert!(checked_sum(vec![u64::MAX, 1]).is_err());
+    assert_eq!(FuelModel::PUZZLE.fuel(u64::MAX), u64::MAX / 3 - 2);
+  }
+}
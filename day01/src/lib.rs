@@ -0,0 +1,29 @@
+use solution::{Answer, Solution};
+
+pub mod fuel;
+
+use fuel::{FuelModel, Report};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+  fn day(&self) -> u8 {
+    1
+  }
+
+  fn part_1(&self, input: &str) -> Result<Answer, String> {
+    Ok(
+      Report::parse(input, &FuelModel::PUZZLE)?
+        .base_fuel()?
+        .into(),
+    )
+  }
+
+  fn part_2(&self, input: &str) -> Result<Answer, String> {
+    Ok(
+      Report::parse(input, &FuelModel::PUZZLE)?
+        .total_fuel()?
+        .into(),
+    )
+  }
+}
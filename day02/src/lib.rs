@@ -0,0 +1,69 @@
+use intcode::{Program, Word};
+use solution::{Answer, Solution};
+
+fn part_1(input: &str) -> Result<Word, String> {
+  let mut program = Program::from_str(input.trim())?;
+  program.write(1, 12)?;
+  program.write(2, 2)?;
+  program.run(&[])?;
+
+  program.read(0)
+}
+
+fn part_2(input: &str) -> Result<Option<Word>, String> {
+  let initial_program = Program::from_str(input.trim())?;
+  let mut program = Program::new(initial_program.mem_size());
+
+  for noun in 0..=99 {
+    for verb in 0..=99 {
+      program.mimick(&initial_program);
+      program.write(1, noun)?;
+      program.write(2, verb)?;
+
+      program.run(&[])?;
+
+      if program.read(0)? == 19690720 {
+        return Ok(Some(100 * noun + verb));
+      }
+    }
+  }
+
+  Ok(None)
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+  fn day(&self) -> u8 {
+    2
+  }
+
+  fn part_1(&self, input: &str) -> Result<Answer, String> {
+    Ok(part_1(input)?.into())
+  }
+
+  fn part_2(&self, input: &str) -> Result<Answer, String> {
+    Ok(part_2(input)?.into())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn simple_program() {
+    const INPUT_TEST: &str = "1,9,10,3,2,3,11,0,99,30,40,50";
+
+    let mut program = Program::from_str(&INPUT_TEST[0..INPUT_TEST.len()]).unwrap();
+    program.run(&[]).unwrap();
+
+    assert_eq!(program.read(0).unwrap(), 3500);
+
+    const INPUT_TEST_2: &str = "1,1,1,4,99,5,6,0,99";
+    program = Program::from_str(&INPUT_TEST_2[0..INPUT_TEST_2.len()]).unwrap();
+    program.run(&[]).unwrap();
+
+    assert_eq!(program.read(0).unwrap(), 30);
+  }
+}
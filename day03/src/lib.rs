@@ -0,0 +1,83 @@
+use solution::{Answer, Solution};
+
+pub mod svg;
+pub mod wires;
+
+use wires::{parse_wires, Crossings};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+  fn day(&self) -> u8 {
+    3
+  }
+
+  fn part_1(&self, input: &str) -> Result<Answer, String> {
+    let crossings = Crossings::new(&parse_wires(input)?, false);
+    let distance = crossings
+      .closest(2)
+      .map(|p| p.manhattan(grid::Point::ORIGIN));
+    Ok(distance.into())
+  }
+
+  fn part_2(&self, input: &str) -> Result<Answer, String> {
+    let crossings = Crossings::new(&parse_wires(input)?, false);
+    Ok(crossings.fewest_steps(&[0, 1]).into())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use grid::Point;
+
+  fn closest_distance(input: &str) -> Option<u64> {
+    let crossings = Crossings::new(&parse_wires(input).unwrap(), false);
+    crossings.closest(2).map(|p| p.manhattan(Point::ORIGIN))
+  }
+
+  fn best_steps(input: &str) -> Option<u64> {
+    Crossings::new(&parse_wires(input).unwrap(), false).fewest_steps(&[0, 1])
+  }
+
+  const INPUT_TEST_1: &str = r#"R75,D30,R83,U83,L12,D49,R71,U7,L72
+U62,R66,U55,R34,D71,R55,D58,R83"#;
+  const INPUT_TEST_2: &str = r#"R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51
+U98,R91,D20,R16,D67,R40,U7,R15,U6,R07"#;
+
+  #[test]
+  fn test_distance_1() {
+    let result = closest_distance(INPUT_TEST_1).unwrap();
+    assert_eq!(result, 159);
+  }
+
+  #[test]
+  fn test_distance_2() {
+    let result = closest_distance(INPUT_TEST_2).unwrap();
+    assert_eq!(result, 135);
+  }
+
+  #[test]
+  fn test_steps_1() {
+    let result = best_steps(INPUT_TEST_1).unwrap();
+    assert_eq!(result, 610);
+  }
+
+  #[test]
+  fn test_steps_2() {
+    let result = best_steps(INPUT_TEST_2).unwrap();
+    assert_eq!(result, 410);
+  }
+
+  #[test]
+  fn test_overlaps() {
+    // the wires share the segment from (2, 0) to (5, 0), the second one going backwards
+    let input = "R5,D3\nU1,R7,D1,L5";
+    assert_eq!(closest_distance(input), Some(2));
+    assert_eq!(best_steps(input), Some(5 + 1 + 7 + 1 + 2));
+
+    // both wires leave the origin the same way
+    assert_eq!(closest_distance("R3\nR2,U1"), Some(1));
+    assert_eq!(best_steps("R3\nR2,U1"), Some(2));
+  }
+}
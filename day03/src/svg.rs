@@ -0,0 +1,97 @@
+//! Drawing of wires as SVG images.
+
+use crate::wires::Wire;
+use grid::{BoundingBox, Point};
+use std::fs;
+use std::path::Path;
+
+/// Colours of the wires, reused when there are more wires than colours.
+const WIRE_COLOURS: &[&str] = &[
+  "#1f77b4", "#ff7f0e", "#2ca02c", "#9467bd", "#8c564b", "#17becf",
+];
+const ORIGIN_COLOUR: &str = "#000000";
+const HIGHLIGHT_COLOUR: &str = "#d62728";
+
+/// Draw the wires, each in its own colour, along with the origin and the highlighted points.
+pub fn render(wires: &[Wire], highlights: &[Point]) -> String {
+  let paths: Vec<_> = wires.iter().map(Wire::path).collect();
+  let bbox = BoundingBox::from_points(paths.iter().flatten().chain(highlights).cloned())
+    .unwrap_or_else(|| BoundingBox::new(Point::ORIGIN));
+
+  // keep lines and marks visible whatever the size of the wires
+  let size = bbox.width().max(bbox.height()) as f64;
+  let stroke = (size / 1000.).max(0.1);
+  let radius = stroke * 4.;
+  let margin = radius * 2.;
+
+  let mut svg = format!(
+    "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n",
+    bbox.min.x as f64 - margin,
+    bbox.min.y as f64 - margin,
+    (bbox.width() - 1) as f64 + margin * 2.,
+    (bbox.height() - 1) as f64 + margin * 2.
+  );
+
+  for (i, path) in paths.iter().enumerate() {
+    let points: Vec<_> = path.iter().map(|p| format!("{},{}", p.x, p.y)).collect();
+
+    svg.push_str(&format!(
+      "  <polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"/>\n",
+      points.join(" "),
+      WIRE_COLOURS[i % WIRE_COLOURS.len()],
+      stroke
+    ));
+  }
+
+  svg.push_str(&circle(Point::ORIGIN, radius, ORIGIN_COLOUR));
+
+  for &p in highlights {
+    svg.push_str(&circle(p, radius, HIGHLIGHT_COLOUR));
+  }
+
+  svg.push_str("</svg>\n");
+  svg
+}
+
+fn circle(p: Point, radius: f64, colour: &str) -> String {
+  format!(
+    "  <circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"/>\n",
+    p.x, p.y, radius, colour
+  )
+}
+
+pub fn save<P>(wires: &[Wire], highlights: &[Point], path: P) -> Result<(), String>
+where
+  P: AsRef<Path>,
+{
+  let path = path.as_ref();
+  fs::write(path, render(wires, highlights))
+    .map_err(|e| format!("cannot write image {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::wires::parse_wires;
+
+  #[test]
+  fn svg() {
+    let wires = parse_wires("R8,U5,L5,D3\nU7,R6,D4,L4").unwrap();
+    let svg = render(&wires, &[Point::new(3, -3)]);
+    let lines: Vec<_> = svg.lines().collect();
+
+    assert_eq!(
+      lines,
+      vec![
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-0.8 -7.8 9.6 8.6\">",
+        "  <polyline points=\"0,0 8,0 8,-5 3,-5 3,-2\" fill=\"none\" stroke=\"#1f77b4\" \
+         stroke-width=\"0.1\"/>",
+        "  <polyline points=\"0,0 0,-7 6,-7 6,-3 2,-3\" fill=\"none\" stroke=\"#ff7f0e\" \
+         stroke-width=\"0.1\"/>",
+        "  <circle cx=\"0\" cy=\"0\" r=\"0.4\" fill=\"#000000\"/>",
+        "  <circle cx=\"3\" cy=\"-3\" r=\"0.4\" fill=\"#d62728\"/>",
+        "</svg>",
+      ]
+    );
+  }
+}
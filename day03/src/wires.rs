@@ -0,0 +1,487 @@
+//! Wires made of horizontal and vertical segments, and the points where they cross.
+
+use grid::{Direction, Point};
+use parse::Line;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::convert::TryFrom;
+
+/// A straight, non-empty part of a wire.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Segment {
+  /// Index of the wire the segment belongs to.
+  wire: usize,
+  start: Point,
+  end: Point,
+  /// Steps taken along the wire to reach the start of the segment.
+  steps: u64,
+}
+
+impl Segment {
+  fn is_horizontal(&self) -> bool {
+    self.start.y == self.end.y
+  }
+
+  /// Coordinate of the line the segment lies on, and range of the other coordinate.
+  fn axis(&self) -> (i64, i64, i64) {
+    if self.is_horizontal() {
+      let (lo, hi) = min_max(self.start.x, self.end.x);
+      (self.start.y, lo, hi)
+    } else {
+      let (lo, hi) = min_max(self.start.y, self.end.y);
+      (self.start.x, lo, hi)
+    }
+  }
+
+  /// Point of the segment whose coordinate along its line is `t`.
+  fn at(&self, t: i64) -> Point {
+    if self.is_horizontal() {
+      Point::new(t, self.start.y)
+    } else {
+      Point::new(self.start.x, t)
+    }
+  }
+
+  /// Steps taken along the wire to reach `p`, a point of the segment.
+  fn steps_to(&self, p: Point) -> u64 {
+    self.steps + self.start.manhattan(p)
+  }
+}
+
+fn min_max(a: i64, b: i64) -> (i64, i64) {
+  (a.min(b), a.max(b))
+}
+
+/// A wire, starting at the origin, made out of horizontal and vertical segments.
+#[derive(Clone, Debug)]
+pub struct Wire {
+  horizontal: Vec<Segment>,
+  vertical: Vec<Segment>,
+}
+
+impl Wire {
+  /// Read the moves of the wire, such as `R8,U5,L5,D3`; `wire` is its index among the wires.
+  fn parse(wire: usize, line: Line) -> parse::Result<Self> {
+    let mut horizontal = Vec::new();
+    let mut vertical = Vec::new();
+    let mut start = Point::ORIGIN;
+    let mut steps = 0;
+
+    for dir in line.text().split(',') {
+      let dir = dir.trim();
+      let mut chars = dir.chars();
+      let letter = chars
+        .next()
+        .ok_or_else(|| line.error(dir, "expected a move, such as R8"))?;
+      let direction = Direction::from_letter(letter)
+        .ok_or_else(|| line.error(dir, format!("wrong direction: {}", letter)))?;
+      let value: u64 = line.parse(chars.as_str())?;
+
+      if value == 0 {
+        continue;
+      }
+
+      let too_far = || line.error(dir, "the wire goes too far");
+      let length = i64::try_from(value).map_err(|_| too_far())?;
+      let delta = direction.delta();
+      let end = Point::new(
+        delta
+          .x
+          .checked_mul(length)
+          .and_then(|dx| start.x.checked_add(dx))
+          .ok_or_else(too_far)?,
+        delta
+          .y
+          .checked_mul(length)
+          .and_then(|dy| start.y.checked_add(dy))
+          .ok_or_else(too_far)?,
+      );
+      let segment = Segment {
+        wire,
+        start,
+        end,
+        steps,
+      };
+
+      if segment.is_horizontal() {
+        horizontal.push(segment);
+      } else {
+        vertical.push(segment);
+      }
+
+      start = end;
+      steps = steps.checked_add(value).ok_or_else(too_far)?;
+    }
+
+    Ok(Wire {
+      horizontal,
+      vertical,
+    })
+  }
+}
+
+impl Wire {
+  /// Corners of the wire, in order, starting with the origin.
+  pub fn path(&self) -> Vec<Point> {
+    let mut segments: Vec<_> = self.horizontal.iter().chain(&self.vertical).collect();
+    segments.sort_unstable_by_key(|segment| segment.steps);
+
+    let mut path = vec![Point::ORIGIN];
+    path.extend(segments.iter().map(|segment| segment.end));
+    path
+  }
+}
+
+/// Wires of the input, one per line.
+pub fn parse_wires(input: &str) -> parse::Result<Vec<Wire>> {
+  parse::lines(input)
+    .enumerate()
+    .map(|(i, line)| Wire::parse(i, line))
+    .collect()
+}
+
+/// A point where wires cross.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Crossing {
+  point: Point,
+  /// Steps taken by each wire to reach the point, every time it does.
+  visits: BTreeMap<usize, BTreeSet<u64>>,
+}
+
+impl Crossing {
+  pub fn point(&self) -> Point {
+    self.point
+  }
+
+  /// Indices of the wires going through the point.
+  pub fn wires(&self) -> impl Iterator<Item = usize> + '_ {
+    self.visits.keys().cloned()
+  }
+
+  /// Number of times wires go through the point, a wire crossing itself counting several times.
+  pub fn passes(&self) -> usize {
+    self.visits.values().map(BTreeSet::len).sum()
+  }
+
+  /// Steps taken by a wire to reach the point for the first time.
+  pub fn steps(&self, wire: usize) -> Option<u64> {
+    self.visits.get(&wire)?.iter().next().cloned()
+  }
+
+  /// Combined steps taken by some wires to reach the point, if they all do.
+  pub fn combined_steps(&self, wires: &[usize]) -> Option<u64> {
+    wires.iter().map(|&wire| self.steps(wire)).sum()
+  }
+}
+
+/// Points where wires cross, but the origin they all start from.
+///
+/// Where wires overlap, only the points that can be the closest or take the fewest steps are kept.
+#[derive(Clone, Debug)]
+pub struct Crossings {
+  crossings: Vec<Crossing>,
+  self_crossings: bool,
+}
+
+impl Crossings {
+  /// Find the crossings of the wires; if `self_crossings` is set, a wire crossing itself counts as
+  /// a crossing, and counts as many times as it goes through a point.
+  pub fn new(wires: &[Wire], self_crossings: bool) -> Self {
+    let mut visits = Visits {
+      points: HashMap::new(),
+      self_crossings,
+    };
+
+    let horizontal: Vec<_> = wires.iter().flat_map(|w| &w.horizontal).collect();
+    let vertical: Vec<_> = wires.iter().flat_map(|w| &w.vertical).collect();
+
+    visits.perpendicular(&horizontal, &vertical);
+    visits.parallel(&horizontal);
+    visits.parallel(&vertical);
+
+    let mut crossings = Crossings {
+      crossings: visits
+        .points
+        .into_iter()
+        .filter(|(point, _)| *point != Point::ORIGIN)
+        .map(|(point, visits)| Crossing { point, visits })
+        .collect(),
+      self_crossings,
+    };
+
+    // segments following each other share a point, which is not a crossing
+    let crossed: Vec<_> = crossings.at_least(2).cloned().collect();
+    crossings.crossings = crossed;
+    crossings.crossings.sort_unstable_by_key(Crossing::point);
+    crossings
+  }
+
+  /// How many times a point is crossed: by distinct wires, or by all passes of the wires if
+  /// self-crossings are counted.
+  pub fn count(&self, crossing: &Crossing) -> usize {
+    if self.self_crossings {
+      crossing.passes()
+    } else {
+      crossing.visits.len()
+    }
+  }
+
+  /// Crossings, sorted by point.
+  pub fn iter(&self) -> impl Iterator<Item = &Crossing> {
+    self.crossings.iter()
+  }
+
+  /// Points crossed at least `k` times.
+  pub fn at_least(&self, k: usize) -> impl Iterator<Item = &Crossing> {
+    self
+      .crossings
+      .iter()
+      .filter(move |crossing| self.count(crossing) >= k)
+  }
+
+  /// Point crossed at least `k` times the closest to the origin.
+  pub fn closest(&self, k: usize) -> Option<Point> {
+    self
+      .at_least(k)
+      .map(Crossing::point)
+      .min_by_key(|p| p.manhattan(Point::ORIGIN))
+  }
+
+  /// Fewest combined steps taken by some wires to reach a point they all go through.
+  pub fn fewest_steps(&self, wires: &[usize]) -> Option<u64> {
+    self
+      .crossings
+      .iter()
+      .filter_map(|crossing| crossing.combined_steps(wires))
+      .min()
+  }
+}
+
+/// Steps taken by each wire to reach the points where segments meet.
+struct Visits {
+  points: HashMap<Point, BTreeMap<usize, BTreeSet<u64>>>,
+  self_crossings: bool,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum Event {
+  Enter(usize),
+  Cross(usize),
+  Leave(usize),
+}
+
+impl Visits {
+  /// Record that `segments` meet at `p`, unless they all belong to the same wire and
+  /// self-crossings are ignored.
+  fn meet(&mut self, segments: &[&Segment], p: Point) {
+    if !self.self_crossings && segments.iter().all(|s| s.wire == segments[0].wire) {
+      return;
+    }
+
+    let visits = self.points.entry(p).or_default();
+
+    for segment in segments {
+      visits
+        .entry(segment.wire)
+        .or_default()
+        .insert(segment.steps_to(p));
+    }
+  }
+
+  /// Sweep a vertical line from left to right, keeping the horizontal segments it crosses sorted by
+  /// `y`, so that each vertical segment only looks at the horizontal segments it crosses.
+  fn perpendicular(&mut self, horizontal: &[&Segment], vertical: &[&Segment]) {
+    let mut events = Vec::with_capacity(horizontal.len() * 2 + vertical.len());
+
+    for (i, h) in horizontal.iter().enumerate() {
+      let (_, lo, hi) = h.axis();
+      events.push((lo, Event::Enter(i)));
+      events.push((hi, Event::Leave(i)));
+    }
+
+    for (i, v) in vertical.iter().enumerate() {
+      events.push((v.start.x, Event::Cross(i)));
+    }
+
+    events.sort_unstable();
+
+    let mut active = BTreeSet::new();
+
+    for (x, event) in events {
+      match event {
+        Event::Enter(i) => {
+          active.insert((horizontal[i].start.y, i));
+        }
+
+        Event::Leave(i) => {
+          active.remove(&(horizontal[i].start.y, i));
+        }
+
+        Event::Cross(i) => {
+          let v = vertical[i];
+          let (_, lo, hi) = v.axis();
+
+          for &(y, j) in active.range((lo, 0)..=(hi, usize::MAX)) {
+            self.meet(&[horizontal[j], v], Point::new(x, y));
+          }
+        }
+      }
+    }
+  }
+
+  /// Sweep segments of the same orientation line by line, and along each line from the lowest
+  /// coordinate to the highest one, splitting it into intervals overlapped by the same segments.
+  fn parallel(&mut self, segments: &[&Segment]) {
+    let mut events = Vec::with_capacity(segments.len() * 2);
+
+    // segments enter before others leave, so that segments sharing an end overlap there
+    for (i, segment) in segments.iter().enumerate() {
+      let (line, lo, hi) = segment.axis();
+      events.push((line, lo, Event::Enter(i)));
+      events.push((line, hi, Event::Leave(i)));
+    }
+
+    events.sort_unstable();
+
+    let mut active = BTreeMap::new();
+    let mut from = None;
+
+    for (_, t, event) in events {
+      match event {
+        Event::Enter(i) => {
+          if let Some(from) = from.filter(|&from| from < t) {
+            self.overlap(&active, from, t - 1);
+          }
+
+          active.insert(i, segments[i]);
+          from = Some(t);
+        }
+
+        Event::Leave(i) => {
+          if let Some(from) = from.filter(|&from| from <= t) {
+            self.overlap(&active, from, t);
+          }
+
+          active.remove(&i);
+          from = t.checked_add(1);
+        }
+
+        Event::Cross(_) => unreachable!(),
+      }
+    }
+  }
+
+  /// Record the points of the interval from `lo` to `hi` overlapped by `segments`, which all lie on
+  /// the same line.
+  ///
+  /// Steps are linear and distances convex along the interval, so the best points are at its ends or
+  /// around the origin; their neighbours stand in for them when they are the origin.
+  fn overlap(&mut self, segments: &BTreeMap<usize, &Segment>, lo: i64, hi: i64) {
+    if segments.len() < 2 {
+      return;
+    }
+
+    let segments: Vec<_> = segments.values().cloned().collect();
+    let mut ts = vec![lo, lo.saturating_add(1), hi.saturating_sub(1), hi, -1, 0, 1];
+    ts.retain(|t| (lo..=hi).contains(t));
+    ts.sort_unstable();
+    ts.dedup();
+
+    for t in ts {
+      self.meet(&segments, segments[0].at(t));
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parsing() {
+    let wires = parse_wires("R8, U5 ,L5,D3\r\n\r\n U7,R0,R6,D4,L4 \r").unwrap();
+    assert_eq!(wires.len(), 2);
+    assert_eq!(
+      wires[0].path(),
+      vec![
+        Point::ORIGIN,
+        Point::new(8, 0),
+        Point::new(8, -5),
+        Point::new(3, -5),
+        Point::new(3, -2)
+      ]
+    );
+    assert_eq!(wires[1].path().len(), 5);
+
+    let e = parse_wires("R8,U5\nR2,,U1").unwrap_err();
+    assert_eq!((e.line, e.column), (2, 4));
+
+    let e = parse_wires("R8,Ü5").unwrap_err();
+    assert_eq!((e.line, e.column), (1, 4));
+    assert_eq!(e.message, "wrong direction: Ü");
+
+    let e = parse_wires("R8,U٣").unwrap_err();
+    assert_eq!((e.line, e.column), (1, 5));
+
+    let e = parse_wires("R9223372036854775807,R1").unwrap_err();
+    assert_eq!((e.line, e.column), (1, 22));
+    assert!(parse_wires("L9223372036854775808").is_err());
+  }
+
+  #[test]
+  fn many_wires() {
+    // the three wires go through (2, -2), the second one reaching it through (1, 0)
+    let wires = parse_wires("U2,R3\nR2,U3\nD1,R1,U3,R2").unwrap();
+    let crossings = Crossings::new(&wires, false);
+
+    let points: Vec<_> = crossings.at_least(3).map(Crossing::point).collect();
+    assert_eq!(points, vec![Point::new(2, -2)]);
+    assert_eq!(crossings.at_least(2).count(), 4);
+    assert_eq!(crossings.closest(2), Some(Point::new(1, 0)));
+    assert_eq!(crossings.closest(3), Some(Point::new(2, -2)));
+    assert_eq!(crossings.closest(4), None);
+
+    assert_eq!(crossings.fewest_steps(&[0, 1]), Some(4 + 4));
+    assert_eq!(crossings.fewest_steps(&[0, 2]), Some(3 + 5));
+    assert_eq!(crossings.fewest_steps(&[1, 2]), Some(1 + 3));
+    assert_eq!(crossings.fewest_steps(&[0, 1, 2]), Some(4 + 4 + 6));
+    assert_eq!(crossings.fewest_steps(&[0, 3]), None);
+  }
+
+  #[test]
+  fn self_crossings() {
+    // the first wire goes back over (2, 0) and (3, 0), and crosses itself at (1, 0)
+    let wires = parse_wires("R4,L2,U1,L1,D2\nD1,R3,U1").unwrap();
+
+    let crossings = Crossings::new(&wires, false);
+    assert_eq!(crossings.closest(2), Some(Point::new(1, 1)));
+    assert_eq!(crossings.closest(3), None);
+
+    let crossings = Crossings::new(&wires, true);
+    assert_eq!(crossings.closest(2), Some(Point::new(1, 0)));
+    assert_eq!(crossings.closest(3), Some(Point::new(3, 0)));
+    assert_eq!(crossings.fewest_steps(&[0]), Some(1));
+
+    let crossing = crossings.iter().find(|c| c.point() == Point::new(3, 0));
+    assert_eq!(crossing.map(Crossing::passes), Some(3));
+    assert_eq!(crossing.and_then(|c| c.steps(0)), Some(3));
+  }
+
+  #[test]
+  fn long_overlaps() {
+    // only the ends of the overlaps and the points around the origin are looked at
+    let wires = parse_wires("R3000000\nL5,R3000005").unwrap();
+    let crossings = Crossings::new(&wires, false);
+
+    assert_eq!(crossings.closest(2), Some(Point::new(1, 0)));
+    assert_eq!(crossings.fewest_steps(&[0, 1]), Some(1 + 11));
+    assert!(crossings.iter().count() < 10);
+
+    // the first wire goes back over itself, and then over the second one
+    let wires = parse_wires("L3000000,R6000000\nL3000000").unwrap();
+    let crossings = Crossings::new(&wires, true);
+
+    assert_eq!(crossings.closest(3), Some(Point::new(-1, 0)));
+    assert_eq!(crossings.closest(2), Some(Point::new(-1, 0)));
+    assert_eq!(crossings.fewest_steps(&[0, 1]), Some(1 + 1));
+  }
+}
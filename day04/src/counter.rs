@@ -0,0 +1,311 @@
+//! Counting of the passwords of a range, whatever their length and base.
+//!
+//! Passwords are sequences of digits, most significant first, all of the same length; leading
+//! zeros are digits like any other.
+
+use crate::rules::{self, Rule};
+use std::collections::HashMap;
+
+/// Length of the runs of equal adjacent digits a password must have at least one of.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Run {
+  pub min: usize,
+  pub max: Option<usize>,
+}
+
+impl Run {
+  fn matches(&self, len: usize) -> bool {
+    let below_max = match self.max {
+      Some(max) => len <= max,
+      None => true,
+    };
+
+    len >= self.min && below_max
+  }
+
+  /// Length from which all runs are alike.
+  fn cap(&self) -> usize {
+    self.max.map_or(self.min, |max| max + 1).max(1)
+  }
+}
+
+/// Criteria that can be counted without enumerating passwords.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Criteria {
+  /// Digits never decrease from left to right.
+  pub non_decreasing: bool,
+  pub run: Option<Run>,
+}
+
+impl Criteria {
+  /// Criteria of a part of the puzzle.
+  pub fn puzzle(part: u8) -> Option<Self> {
+    let max = match part {
+      1 => None,
+      2 => Some(2),
+      _ => return None,
+    };
+
+    Some(Criteria {
+      non_decreasing: true,
+      run: Some(Run { min: 2, max }),
+    })
+  }
+}
+
+impl Rule for Criteria {
+  fn check(&self, digits: &[u8]) -> bool {
+    let non_decreasing = !self.non_decreasing || digits.windows(2).all(|w| w[0] <= w[1]);
+    let run = match self.run {
+      Some(run) => rules::runs(digits).any(|len| run.matches(len)),
+      None => true,
+    };
+
+    non_decreasing && run
+  }
+}
+
+fn check_range(lower: &[u8], upper: &[u8], base: u8) -> Result<(), String> {
+  if lower.len() != upper.len() {
+    return Err(format!(
+      "bounds have different lengths: {} and {}",
+      lower.len(),
+      upper.len()
+    ));
+  }
+
+  match lower.iter().chain(upper).find(|&&digit| digit >= base) {
+    Some(digit) => Err(format!("{} is not a digit in base {}", digit, base)),
+    None => Ok(()),
+  }
+}
+
+/// What is known of a password whose first digits are chosen.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct State {
+  last: Option<u8>,
+  /// Length of the run of the last digit, capped.
+  run: usize,
+  /// A run of the right length was found.
+  found: bool,
+  /// The digits chosen so far are those of the lower bound.
+  at_lower: bool,
+  /// The digits chosen so far are those of the upper bound.
+  at_upper: bool,
+}
+
+/// Number of passwords between `lower` and `upper`, both included, meeting the criteria.
+///
+/// Passwords are built digit by digit, keeping only how many ways there are to reach each state, so
+/// that the work only grows with the length of the passwords.
+pub fn count(lower: &[u8], upper: &[u8], base: u8, criteria: &Criteria) -> Result<u128, String> {
+  check_range(lower, upper, base)?;
+
+  let run = criteria.run.unwrap_or(Run { min: 1, max: None });
+  let cap = run.cap();
+  let mut states = HashMap::new();
+
+  states.insert(
+    State {
+      last: None,
+      run: 0,
+      found: false,
+      at_lower: true,
+      at_upper: true,
+    },
+    1u128,
+  );
+
+  for (&lo, &hi) in lower.iter().zip(upper) {
+    let mut next = HashMap::new();
+
+    for (state, ways) in states {
+      let from = if state.at_lower { lo } else { 0 };
+      let to = if state.at_upper { hi } else { base - 1 };
+      let from = match state.last {
+        Some(last) if criteria.non_decreasing => from.max(last),
+        _ => from,
+      };
+
+      for digit in from..=to {
+        let (run_len, found) = if state.last == Some(digit) {
+          ((state.run + 1).min(cap), state.found)
+        } else {
+          (
+            1,
+            state.found || (state.last.is_some() && run.matches(state.run)),
+          )
+        };
+        let key = State {
+          last: Some(digit),
+          run: run_len,
+          found,
+          at_lower: state.at_lower && digit == lo,
+          at_upper: state.at_upper && digit == hi,
+        };
+
+        *next.entry(key).or_insert(0) += ways;
+      }
+    }
+
+    states = next;
+  }
+
+  Ok(
+    states
+      .into_iter()
+      .filter(|(state, _)| {
+        criteria.run.is_none() || state.found || (state.last.is_some() && run.matches(state.run))
+      })
+      .map(|(_, ways)| ways)
+      .sum(),
+  )
+}
+
+/// Number of passwords between `lower` and `upper`, both included, following `rule`, counted
+/// without enumerating them when the rule can be turned into criteria.
+pub fn count_rule(lower: &[u8], upper: &[u8], base: u8, rule: &dyn Rule) -> Result<u128, String> {
+  match rule.criteria() {
+    Some(criteria) => count(lower, upper, base, &criteria),
+    None => enumerate(lower, upper, base, rule),
+  }
+}
+
+/// Number of passwords between `lower` and `upper`, both included, following `rule`, checking them
+/// one by one.
+pub fn enumerate(lower: &[u8], upper: &[u8], base: u8, rule: &dyn Rule) -> Result<u128, String> {
+  check_range(lower, upper, base)?;
+
+  let mut digits = lower.to_vec();
+  let mut result = 0;
+
+  while digits.as_slice() <= upper {
+    if rule.check(&digits) {
+      result += 1;
+    }
+
+    // increment the password, carrying to the left
+    match digits.iter().rposition(|&digit| digit < base - 1) {
+      Some(i) => {
+        digits[i] += 1;
+        digits[i + 1..].iter_mut().for_each(|digit| *digit = 0);
+      }
+
+      None => break,
+    }
+  }
+
+  Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules;
+
+  #[test]
+  fn puzzle_criteria() {
+    let (lower, upper) = ([1, 3, 4, 5, 6, 4], [5, 8, 5, 1, 5, 9]);
+
+    for part in 1..=2 {
+      let criteria = Criteria::puzzle(part).unwrap();
+      let expected = enumerate(&lower, &upper, 10, &*rules::puzzle(part).unwrap());
+
+      assert_eq!(count(&lower, &upper, 10, &criteria), expected);
+      assert_eq!(enumerate(&lower, &upper, 10, &criteria), expected);
+    }
+  }
+
+  #[test]
+  fn cross_check() {
+    let runs = [
+      None,
+      Some(Run { min: 2, max: None }),
+      Some(Run {
+        min: 2,
+        max: Some(2),
+      }),
+      Some(Run {
+        min: 1,
+        max: Some(1),
+      }),
+      Some(Run {
+        min: 3,
+        max: Some(4),
+      }),
+    ];
+    let ranges: [(&[u8], &[u8], u8); 4] = [
+      (&[0, 0, 0, 0, 0], &[2, 2, 2, 2, 2], 3),
+      (&[0, 1, 1, 0, 2, 1], &[3, 0, 2, 3, 1, 0], 4),
+      (&[4, 2], &[1, 0], 5),
+      (&[], &[], 2),
+    ];
+
+    for &(lower, upper, base) in &ranges {
+      for &non_decreasing in &[false, true] {
+        for &run in &runs {
+          let criteria = Criteria {
+            non_decreasing,
+            run,
+          };
+
+          assert_eq!(
+            count(lower, upper, base, &criteria),
+            enumerate(lower, upper, base, &criteria),
+            "{:?} in base {} between {:?} and {:?}",
+            criteria,
+            base,
+            lower,
+            upper
+          );
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn large_ranges() {
+    let criteria = Criteria::puzzle(1).unwrap();
+    let nines = [9; 20];
+
+    // non-decreasing passwords of 20 digits: multisets of 20 digits out of 10, C(29, 9)
+    let all = Criteria {
+      non_decreasing: true,
+      run: None,
+    };
+    assert_eq!(count(&[0; 20], &nines, 10, &all), Ok(10_015_005));
+
+    // with more than 10 digits out of 10, there always is a double
+    assert_eq!(count(&[0; 20], &nines, 10, &criteria), Ok(10_015_005));
+    assert_eq!(count(&[0; 12], &[9; 12], 10, &criteria), Ok(293_930));
+
+    // but there isn’t always an exact pair, such as in 00011122…
+    let pairs = count(&[0; 20], &nines, 10, &Criteria::puzzle(2).unwrap()).unwrap();
+    assert!(0 < pairs && pairs < 10_015_005);
+  }
+
+  #[test]
+  fn custom_rules() {
+    let (lower, upper) = ([1, 3, 4, 5], [5, 8, 5, 1]);
+    let odd_last = |digits: &[u8]| matches!(digits.last(), Some(digit) if digit % 2 == 1);
+    let rule = rules::NonDecreasing.and(odd_last);
+
+    assert_eq!(
+      count_rule(&lower, &upper, 10, &rule),
+      enumerate(&lower, &upper, 10, &rule)
+    );
+    assert_ne!(
+      count_rule(&lower, &upper, 10, &rule),
+      count_rule(&lower, &upper, 10, &rules::NonDecreasing)
+    );
+  }
+
+  #[test]
+  fn errors() {
+    let criteria = Criteria::puzzle(1).unwrap();
+
+    assert!(count(&[1, 2], &[1, 2, 3], 10, &criteria).is_err());
+    assert!(count(&[1, 2], &[1, 9], 8, &criteria).is_err());
+    assert!(enumerate(&[1, 2], &[1, 9], 8, &criteria).is_err());
+  }
+}
@@ -0,0 +1,114 @@
+use parse::Line;
+use solution::{Answer, Solution};
+
+pub mod counter;
+pub mod rules;
+
+/// Digits of a password, most significant first.
+fn parse_digits(line: &Line, s: &str) -> parse::Result<Vec<u8>> {
+  let s = s.trim();
+
+  if s.is_empty() {
+    return Err(line.error(s, "expected a number"));
+  }
+
+  s.char_indices()
+    .map(|(i, c)| {
+      c.to_digit(10)
+        .map(|digit| digit as u8)
+        .ok_or_else(|| line.error(&s[i..], format!("not a digit: {:?}", c)))
+    })
+    .collect()
+}
+
+/// Parse the puzzle range, written as `lower-upper`, both bounds having the same number of digits.
+fn parse_range(input: &str) -> parse::Result<(Vec<u8>, Vec<u8>)> {
+  let line = parse::lines(input).next().ok_or_else(|| parse::Error {
+    line: 1,
+    column: 1,
+    message: "missing range".to_owned(),
+  })?;
+  let (lower, upper) = line.pair("-")?;
+  let (lower, upper) = (parse_digits(&line, lower)?, parse_digits(&line, upper)?);
+
+  if lower.len() != upper.len() {
+    return Err(line.error(
+      line.text(),
+      format!(
+        "bounds have different lengths: {} and {}",
+        lower.len(),
+        upper.len()
+      ),
+    ));
+  }
+
+  Ok((lower, upper))
+}
+
+fn solve(input: &str, part: u8) -> Result<Answer, String> {
+  let (lower, upper) = parse_range(input)?;
+  let rule = rules::puzzle(part).ok_or_else(|| format!("no rules for part {}", part))?;
+  let count = counter::count_rule(&lower, &upper, 10, &*rule)?;
+
+  Ok(count.into())
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+  fn day(&self) -> u8 {
+    4
+  }
+
+  fn part_1(&self, input: &str) -> Result<Answer, String> {
+    solve(input, 1)
+  }
+
+  fn part_2(&self, input: &str) -> Result<Answer, String> {
+    solve(input, 2)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn range() {
+    assert_eq!(
+      parse_range("134564-585159\n"),
+      Ok((vec![1, 3, 4, 5, 6, 4], vec![5, 8, 5, 1, 5, 9]))
+    );
+    assert!(parse_range("134564").is_err());
+    assert!(parse_range("13456-585159").is_err());
+    assert!(parse_range("-585159").is_err());
+    assert_eq!(parse_range("12-3é").unwrap_err().column, 5);
+    assert_eq!(parse_range("1345a4-585159").unwrap_err().column, 5);
+    assert!(parse_range("").is_err());
+  }
+
+  #[test]
+  fn counting() {
+    let count = |lower, upper, rule| counter::count_rule(lower, upper, 10, rule).unwrap();
+    let all = |_: &[u8]| true;
+
+    assert_eq!(count(&[0, 0, 0, 0, 9, 8], &[0, 0, 0, 1, 0, 2], &all), 5);
+    assert_eq!(count(&[9, 9, 9, 9, 9, 8], &[9, 9, 9, 9, 9, 9], &all), 2);
+    assert_eq!(
+      count(
+        &[1, 1, 1, 1, 0, 0],
+        &[1, 1, 1, 1, 2, 0],
+        &rules::NonDecreasing
+      ),
+      9
+    );
+  }
+
+  #[test]
+  fn answers() {
+    let input = "134564-585159\n";
+
+    assert_eq!(Day04.part_1(input), Ok(Answer::Number(1929)));
+    assert_eq!(Day04.part_2(input), Ok(Answer::Number(1306)));
+  }
+}
@@ -0,0 +1,229 @@
+//! Rules passwords must follow, composable at runtime.
+//!
+//! Any `Fn(&[u8]) -> bool` closure is a rule, so that new rules don’t need new types.
+
+use crate::counter::{Criteria, Run};
+
+/// A rule about the digits of a password, most significant first.
+pub trait Rule {
+  fn check(&self, digits: &[u8]) -> bool;
+
+  /// Same rule as criteria that can be counted without enumerating passwords, if there are some.
+  fn criteria(&self) -> Option<Criteria> {
+    None
+  }
+
+  /// Both rules must be followed.
+  fn and<R>(self, other: R) -> And<Self, R>
+  where
+    Self: Sized,
+    R: Rule,
+  {
+    And(self, other)
+  }
+
+  /// Either rule must be followed.
+  fn or<R>(self, other: R) -> Or<Self, R>
+  where
+    Self: Sized,
+    R: Rule,
+  {
+    Or(self, other)
+  }
+
+  /// The rule must not be followed.
+  fn not(self) -> Not<Self>
+  where
+    Self: Sized,
+  {
+    Not(self)
+  }
+}
+
+impl<F> Rule for F
+where
+  F: Fn(&[u8]) -> bool,
+{
+  fn check(&self, digits: &[u8]) -> bool {
+    self(digits)
+  }
+}
+
+/// Digits never decrease from left to right.
+#[derive(Clone, Copy, Debug)]
+pub struct NonDecreasing;
+
+impl Rule for NonDecreasing {
+  fn check(&self, digits: &[u8]) -> bool {
+    digits.windows(2).all(|w| w[0] <= w[1])
+  }
+
+  fn criteria(&self) -> Option<Criteria> {
+    Some(Criteria {
+      non_decreasing: true,
+      run: None,
+    })
+  }
+}
+
+/// At least two adjacent digits are the same.
+#[derive(Clone, Copy, Debug)]
+pub struct HasDouble;
+
+impl Rule for HasDouble {
+  fn check(&self, digits: &[u8]) -> bool {
+    digits.windows(2).any(|w| w[0] == w[1])
+  }
+
+  fn criteria(&self) -> Option<Criteria> {
+    Some(Criteria {
+      non_decreasing: false,
+      run: Some(Run { min: 2, max: None }),
+    })
+  }
+}
+
+/// Exactly two adjacent digits are the same, not being part of a larger group.
+#[derive(Clone, Copy, Debug)]
+pub struct HasExactPair;
+
+impl Rule for HasExactPair {
+  fn check(&self, digits: &[u8]) -> bool {
+    runs(digits).any(|len| len == 2)
+  }
+
+  fn criteria(&self) -> Option<Criteria> {
+    Some(Criteria {
+      non_decreasing: false,
+      run: Some(Run {
+        min: 2,
+        max: Some(2),
+      }),
+    })
+  }
+}
+
+/// Lengths of the runs of equal adjacent digits, from left to right.
+pub fn runs(digits: &[u8]) -> impl Iterator<Item = usize> + '_ {
+  let mut rest = digits;
+
+  std::iter::from_fn(move || {
+    let first = *rest.first()?;
+    let len = rest.iter().take_while(|&&digit| digit == first).count();
+    rest = &rest[len..];
+    Some(len)
+  })
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct And<A, B>(A, B);
+
+impl<A, B> Rule for And<A, B>
+where
+  A: Rule,
+  B: Rule,
+{
+  fn check(&self, digits: &[u8]) -> bool {
+    self.0.check(digits) && self.1.check(digits)
+  }
+
+  /// Only when at most one of the rules is about runs, as requiring two different runs can’t be
+  /// counted.
+  fn criteria(&self) -> Option<Criteria> {
+    let (a, b) = (self.0.criteria()?, self.1.criteria()?);
+    let run = match (a.run, b.run) {
+      (Some(_), Some(_)) => return None,
+      (run, None) | (None, run) => run,
+    };
+
+    Some(Criteria {
+      non_decreasing: a.non_decreasing || b.non_decreasing,
+      run,
+    })
+  }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Or<A, B>(A, B);
+
+impl<A, B> Rule for Or<A, B>
+where
+  A: Rule,
+  B: Rule,
+{
+  fn check(&self, digits: &[u8]) -> bool {
+    self.0.check(digits) || self.1.check(digits)
+  }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Not<A>(A);
+
+impl<A> Rule for Not<A>
+where
+  A: Rule,
+{
+  fn check(&self, digits: &[u8]) -> bool {
+    !self.0.check(digits)
+  }
+}
+
+/// Rules of a part of the puzzle.
+pub fn puzzle(part: u8) -> Option<Box<dyn Rule>> {
+  match part {
+    1 => Some(Box::new(NonDecreasing.and(HasDouble))),
+    2 => Some(Box::new(NonDecreasing.and(HasExactPair))),
+    _ => None,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn puzzle_rules() {
+    let part_1 = puzzle(1).unwrap();
+    assert!(part_1.check(&[1, 1, 1, 1, 1, 1]));
+    assert!(!part_1.check(&[2, 2, 3, 4, 5, 0]));
+    assert!(!part_1.check(&[1, 2, 3, 7, 8, 9]));
+
+    let part_2 = puzzle(2).unwrap();
+    assert!(part_2.check(&[1, 1, 2, 2, 3, 3]));
+    assert!(!part_2.check(&[1, 2, 3, 4, 4, 4]));
+    assert!(part_2.check(&[1, 1, 1, 1, 2, 2]));
+
+    assert!(puzzle(3).is_none());
+  }
+
+  #[test]
+  fn countable_rules() {
+    for part in 1..=2 {
+      assert_eq!(puzzle(part).unwrap().criteria(), Criteria::puzzle(part));
+    }
+
+    let no_zero = |digits: &[u8]| !digits.contains(&0);
+    assert!(NonDecreasing.and(no_zero).criteria().is_none());
+    assert!(HasDouble.or(HasExactPair).criteria().is_none());
+    assert!(HasDouble.and(HasExactPair).criteria().is_none());
+  }
+
+  #[test]
+  fn run_lengths() {
+    assert_eq!(runs(&[1, 1, 2, 3, 3, 3]).collect::<Vec<_>>(), vec![2, 1, 3]);
+    assert_eq!(runs(&[]).count(), 0);
+  }
+
+  #[test]
+  fn custom_rules() {
+    let no_zero = |digits: &[u8]| !digits.contains(&0);
+    let rule = HasDouble
+      .or(HasExactPair)
+      .and(no_zero)
+      .and(NonDecreasing.not());
+
+    assert!(rule.check(&[3, 3, 2]));
+    assert!(!rule.check(&[3, 3, 4]));
+    assert!(!rule.check(&[0, 0, 4, 1]));
+  }
+}
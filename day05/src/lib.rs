@@ -0,0 +1,24 @@
+use intcode::{Program, Word};
+use solution::{Answer, Solution};
+
+/// Run the diagnostic program for the given system ID and return its diagnostic code.
+fn diagnostic(input: &str, system_id: Word) -> Result<Option<Word>, String> {
+  let mut program = Program::from_str(input.trim())?;
+  program.run(&[system_id])
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+  fn day(&self) -> u8 {
+    5
+  }
+
+  fn part_1(&self, input: &str) -> Result<Answer, String> {
+    Ok(diagnostic(input, 1)?.into())
+  }
+
+  fn part_2(&self, input: &str) -> Result<Answer, String> {
+    Ok(diagnostic(input, 5)?.into())
+  }
+}
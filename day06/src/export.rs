@@ -0,0 +1,211 @@
+//! Export of orbit trees to Graphviz and to indented text, to debug large maps.
+
+use crate::tree::{Id, OrbitTree};
+use std::collections::HashSet;
+
+/// Objects drawn differently, such as the transfer path between two objects.
+#[derive(Clone, Debug, Default)]
+pub struct Highlight {
+  objects: HashSet<Id>,
+  /// Orbits, as `(orbited, object)`.
+  orbits: HashSet<(Id, Id)>,
+}
+
+impl Highlight {
+  /// Highlight the path between `a` and `b`, if they’re part of the same tree.
+  pub fn path(tree: &OrbitTree, a: Id, b: Id) -> Option<Self> {
+    let path = tree.path(a, b)?;
+    let orbits = path
+      .windows(2)
+      .map(|w| {
+        if tree.parent(w[1]) == Some(w[0]) {
+          (w[0], w[1])
+        } else {
+          (w[1], w[0])
+        }
+      })
+      .collect();
+
+    Some(Highlight {
+      objects: path.into_iter().collect(),
+      orbits,
+    })
+  }
+
+  pub fn contains(&self, id: Id) -> bool {
+    self.objects.contains(&id)
+  }
+}
+
+/// Objects orbiting each object, sorted by name, and roots sorted by name.
+fn children(tree: &OrbitTree) -> (Vec<Vec<Id>>, Vec<Id>) {
+  let mut children = vec![Vec::new(); tree.len()];
+  let mut roots = Vec::new();
+
+  for id in 0..tree.len() {
+    match tree.parent(id) {
+      Some(parent) => children[parent].push(id),
+      None => roots.push(id),
+    }
+  }
+
+  for c in &mut children {
+    c.sort_unstable_by_key(|&id| tree.name(id));
+  }
+
+  roots.sort_unstable_by_key(|&id| tree.name(id));
+  (children, roots)
+}
+
+fn quote(name: &str) -> String {
+  format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Graphviz DOT graph, edges going from orbited objects to the objects orbiting them.
+pub fn dot(tree: &OrbitTree, highlight: &Highlight) -> String {
+  let (children, roots) = children(tree);
+  let mut output = "digraph orbits {\n  rankdir=LR;\n".to_owned();
+
+  let mut highlighted: Vec<_> = highlight.objects.iter().cloned().collect();
+  highlighted.sort_unstable_by_key(|&id| tree.name(id));
+
+  for id in highlighted {
+    output.push_str(&format!(
+      "  {} [color=red, fontcolor=red];\n",
+      quote(tree.name(id))
+    ));
+  }
+
+  // depth-first, so that the output follows the tree
+  let mut stack: Vec<_> = roots.into_iter().rev().collect();
+
+  while let Some(id) = stack.pop() {
+    for &child in &children[id] {
+      let style = if highlight.orbits.contains(&(id, child)) {
+        " [color=red, penwidth=2]"
+      } else {
+        ""
+      };
+
+      output.push_str(&format!(
+        "  {} -> {}{};\n",
+        quote(tree.name(id)),
+        quote(tree.name(child)),
+        style
+      ));
+    }
+
+    stack.extend(children[id].iter().rev());
+  }
+
+  output.push_str("}\n");
+  output
+}
+
+/// Indented tree, one object per line, highlighted objects being marked with a star.
+pub fn text(tree: &OrbitTree, highlight: &Highlight) -> String {
+  let (children, roots) = children(tree);
+  let mut output = String::new();
+
+  // objects to draw along with the prefix of their line and of the lines of their children
+  let mut stack: Vec<_> = roots
+    .into_iter()
+    .rev()
+    .map(|id| (id, String::new(), String::new()))
+    .collect();
+
+  while let Some((id, prefix, indent)) = stack.pop() {
+    output.push_str(&prefix);
+    output.push_str(tree.name(id));
+
+    if highlight.contains(id) {
+      output.push_str(" *");
+    }
+
+    output.push('\n');
+
+    let last = children[id].len().saturating_sub(1);
+
+    for (i, &child) in children[id].iter().enumerate().rev() {
+      let (branch, next) = if i == last {
+        ("└── ", "    ")
+      } else {
+        ("├── ", "│   ")
+      };
+
+      stack.push((
+        child,
+        format!("{}{}", indent, branch),
+        format!("{}{}", indent, next),
+      ));
+    }
+  }
+
+  output
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const MAP: &str = "COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\nK)YOU\nI)SAN\n";
+
+  fn highlight(tree: &OrbitTree) -> Highlight {
+    Highlight::path(tree, tree.id("YOU").unwrap(), tree.id("SAN").unwrap()).unwrap()
+  }
+
+  #[test]
+  fn text_tree() {
+    let tree = OrbitTree::parse(MAP).unwrap();
+
+    assert_eq!(
+      text(&tree, &highlight(&tree)),
+      "COM
+└── B
+    ├── C
+    │   └── D *
+    │       ├── E *
+    │       │   ├── F
+    │       │   └── J *
+    │       │       └── K *
+    │       │           ├── L
+    │       │           └── YOU *
+    │       └── I *
+    │           └── SAN *
+    └── G
+        └── H
+"
+    );
+  }
+
+  #[test]
+  fn graphviz() {
+    let tree = OrbitTree::from_pairs(vec![
+      ("COM", "B"),
+      ("B", "YOU"),
+      ("COM", "SAN"),
+      ("B", "a\"b"),
+    ])
+    .unwrap();
+    let output = dot(&tree, &highlight(&tree));
+    let lines: Vec<_> = output.lines().collect();
+
+    assert_eq!(
+      lines,
+      vec![
+        "digraph orbits {",
+        "  rankdir=LR;",
+        "  \"B\" [color=red, fontcolor=red];",
+        "  \"COM\" [color=red, fontcolor=red];",
+        "  \"SAN\" [color=red, fontcolor=red];",
+        "  \"YOU\" [color=red, fontcolor=red];",
+        "  \"COM\" -> \"B\" [color=red, penwidth=2];",
+        "  \"COM\" -> \"SAN\" [color=red, penwidth=2];",
+        "  \"B\" -> \"YOU\" [color=red, penwidth=2];",
+        "  \"B\" -> \"a\\\"b\";",
+        "}",
+      ]
+    );
+    assert!(!dot(&tree, &Highlight::default()).contains("red"));
+  }
+}
@@ -0,0 +1,69 @@
+use solution::{Answer, Solution};
+
+pub mod export;
+pub mod tree;
+
+use tree::OrbitTree;
+
+/// Transfers needed to orbit the object Santa orbits.
+fn transfers(tree: &OrbitTree) -> Result<usize, String> {
+  let object = |name| {
+    tree
+      .id(name)
+      .filter(|&id| tree.parent(id).is_some())
+      .ok_or_else(|| format!("{} is not orbiting anything", name))
+  };
+
+  tree
+    .transfers(object("YOU")?, object("SAN")?)
+    .ok_or_else(|| "YOU and SAN are not orbiting the same center of mass".to_owned())
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+  fn day(&self) -> u8 {
+    6
+  }
+
+  fn part_1(&self, input: &str) -> Result<Answer, String> {
+    Ok(OrbitTree::parse(input)?.total_orbits().into())
+  }
+
+  fn part_2(&self, input: &str) -> Result<Answer, String> {
+    Ok(transfers(&OrbitTree::parse(input)?)?.into())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const TEST_MAP: &str = r#"
+    COM)B
+    B)C
+    C)D
+    D)E
+    E)F
+    B)G
+    G)H
+    D)I
+    E)J
+    J)K
+    K)L
+  "#;
+
+  #[test]
+  fn test_map() {
+    let tree = OrbitTree::parse(TEST_MAP).unwrap();
+    let count = tree.total_orbits();
+    assert_eq!(count, 42);
+  }
+
+  #[test]
+  fn test_transfers() {
+    let tree = OrbitTree::parse(&format!("{}K)YOU\nI)SAN\n", TEST_MAP)).unwrap();
+    assert_eq!(transfers(&tree), Ok(4));
+    assert!(transfers(&OrbitTree::parse(TEST_MAP).unwrap()).is_err());
+  }
+}
@@ -0,0 +1,461 @@
+//! Tree of objects orbiting each other, answering queries about any pair of objects.
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// Interned name of an object.
+pub type Id = usize;
+
+/// Something wrong in a map of orbits; lines start at 1.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Problem {
+  /// An object orbits several objects.
+  TwoParents { object: String, lines: Vec<usize> },
+  /// Objects orbit each other in a loop.
+  Cycle {
+    objects: Vec<String>,
+    lines: Vec<usize>,
+  },
+  /// Objects don’t orbit a root, but a cycle.
+  Unreachable {
+    objects: Vec<String>,
+    lines: Vec<usize>,
+  },
+  /// Several objects orbit nothing; lines are those where they first appear.
+  MultipleRoots {
+    roots: Vec<String>,
+    lines: Vec<usize>,
+  },
+}
+
+impl fmt::Display for Problem {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let (what, lines) = match self {
+      Problem::TwoParents { object, lines } => {
+        (format!("{} orbits several objects", object), lines)
+      }
+      Problem::Cycle { objects, lines } => (format!("cycle through {}", objects.join(", ")), lines),
+      Problem::Unreachable { objects, lines } => {
+        (format!("{} only orbit a cycle", objects.join(", ")), lines)
+      }
+      Problem::MultipleRoots { roots, lines } => {
+        (format!("several roots: {}", roots.join(", ")), lines)
+      }
+    };
+    let lines: Vec<_> = lines.iter().map(usize::to_string).collect();
+
+    write!(f, "{} (lines {})", what, lines.join(", "))
+  }
+}
+
+/// Error reading a map of orbits.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OrbitError {
+  Syntax(parse::Error),
+  /// Every problem of the map.
+  Invalid(Vec<Problem>),
+}
+
+impl fmt::Display for OrbitError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      OrbitError::Syntax(e) => e.fmt(f),
+      OrbitError::Invalid(problems) => {
+        let problems: Vec<_> = problems.iter().map(Problem::to_string).collect();
+        write!(f, "invalid orbits: {}", problems.join("; "))
+      }
+    }
+  }
+}
+
+impl std::error::Error for OrbitError {}
+
+impl From<parse::Error> for OrbitError {
+  fn from(e: parse::Error) -> Self {
+    OrbitError::Syntax(e)
+  }
+}
+
+impl From<OrbitError> for String {
+  fn from(e: OrbitError) -> Self {
+    e.to_string()
+  }
+}
+
+/// How an object was reached while computing depths.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Mark {
+  Unvisited,
+  /// Being walked up from the given object.
+  Walking(Id),
+  Depth(usize),
+  /// Part of a cycle, or orbiting one.
+  Lost,
+}
+
+/// Objects, each orbiting exactly one other object but a single root.
+#[derive(Clone, Debug, Default)]
+pub struct OrbitTree {
+  names: Vec<String>,
+  ids: HashMap<String, Id>,
+  parents: Vec<Option<Id>>,
+  /// Line where each object first appears.
+  first_lines: Vec<usize>,
+  /// Lines saying what each object orbits.
+  orbit_lines: Vec<Vec<usize>>,
+  depths: Vec<usize>,
+}
+
+impl OrbitTree {
+  /// Read a map of orbits, one `A)B` per line, `B` orbiting `A`.
+  pub fn parse(input: &str) -> Result<Self, OrbitError> {
+    let orbits = parse::records(input, |line| {
+      let (orbited, object) = line.pair(")")?;
+      Ok((line.number(), orbited.trim(), object.trim()))
+    })?;
+
+    Self::build(orbits)
+  }
+
+  /// Build the tree from `(orbited, object)` pairs, numbered from 1 as lines are.
+  pub fn from_pairs<'a, I>(pairs: I) -> Result<Self, OrbitError>
+  where
+    I: IntoIterator<Item = (&'a str, &'a str)>,
+  {
+    Self::build(
+      pairs
+        .into_iter()
+        .zip(1..)
+        .map(|((orbited, object), line)| (line, orbited, object)),
+    )
+  }
+
+  fn build<'a, I>(orbits: I) -> Result<Self, OrbitError>
+  where
+    I: IntoIterator<Item = (usize, &'a str, &'a str)>,
+  {
+    let mut tree = OrbitTree::default();
+
+    for (line, orbited, object) in orbits {
+      let orbited = tree.intern(orbited, line);
+      let object = tree.intern(object, line);
+
+      if tree.parents[object].is_none() {
+        tree.parents[object] = Some(orbited);
+      }
+
+      tree.orbit_lines[object].push(line);
+    }
+
+    let mut problems: Vec<_> = (0..tree.len())
+      .filter(|&id| tree.orbit_lines[id].len() > 1)
+      .map(|id| Problem::TwoParents {
+        object: tree.names[id].clone(),
+        lines: tree.orbit_lines[id].clone(),
+      })
+      .collect();
+
+    let roots: Vec<_> = (0..tree.len())
+      .filter(|&id| tree.parents[id].is_none())
+      .collect();
+
+    if roots.len() > 1 {
+      problems.push(Problem::MultipleRoots {
+        roots: roots.iter().map(|&id| tree.names[id].clone()).collect(),
+        lines: roots.iter().map(|&id| tree.first_lines[id]).collect(),
+      });
+    }
+
+    let (depths, mut lost) = tree.compute_depths();
+    problems.append(&mut lost);
+
+    if problems.is_empty() {
+      tree.depths = depths;
+      Ok(tree)
+    } else {
+      Err(OrbitError::Invalid(problems))
+    }
+  }
+
+  fn intern(&mut self, name: &str, line: usize) -> Id {
+    if let Some(&id) = self.ids.get(name) {
+      return id;
+    }
+
+    let id = self.names.len();
+    self.names.push(name.to_owned());
+    self.ids.insert(name.to_owned(), id);
+    self.parents.push(None);
+    self.first_lines.push(line);
+    self.orbit_lines.push(Vec::new());
+    id
+  }
+
+  /// Depth of every object, each one being computed once from the depth of what it orbits, along
+  /// with the cycles and the objects orbiting them.
+  fn compute_depths(&self) -> (Vec<usize>, Vec<Problem>) {
+    let mut marks = vec![Mark::Unvisited; self.len()];
+    let mut problems = Vec::new();
+    let mut unreachable = Vec::new();
+
+    for id in 0..self.len() {
+      // walk up until an object of known depth, a root, or an object seen during this very walk
+      let mut chain: Vec<Id> = Vec::new();
+      let mut current = Some(id);
+
+      let mut depth = loop {
+        let object = match current {
+          Some(object) => object,
+          None => break Some(0),
+        };
+
+        match marks[object] {
+          Mark::Depth(depth) => break Some(depth + 1),
+          Mark::Lost => break None,
+          Mark::Walking(walk) if walk == id => {
+            let start = chain.iter().position(|&o| o == object).unwrap_or(0);
+            let cycle = chain.split_off(start);
+
+            for &o in &cycle {
+              marks[o] = Mark::Lost;
+            }
+
+            problems.push(Problem::Cycle {
+              objects: cycle.iter().map(|&o| self.names[o].clone()).collect(),
+              lines: self.lines_of(&cycle),
+            });
+            break None;
+          }
+          _ => {
+            marks[object] = Mark::Walking(id);
+            chain.push(object);
+            current = self.parents[object];
+          }
+        }
+      };
+
+      for &object in chain.iter().rev() {
+        marks[object] = match depth {
+          Some(d) => {
+            depth = Some(d + 1);
+            Mark::Depth(d)
+          }
+
+          None => {
+            unreachable.push(object);
+            Mark::Lost
+          }
+        };
+      }
+    }
+
+    if !unreachable.is_empty() {
+      unreachable.sort_unstable();
+      problems.push(Problem::Unreachable {
+        objects: unreachable.iter().map(|&o| self.names[o].clone()).collect(),
+        lines: self.lines_of(&unreachable),
+      });
+    }
+
+    let depths = marks
+      .into_iter()
+      .map(|mark| match mark {
+        Mark::Depth(depth) => depth,
+        _ => 0,
+      })
+      .collect();
+
+    (depths, problems)
+  }
+
+  /// Sorted lines saying what the objects orbit.
+  fn lines_of(&self, objects: &[Id]) -> Vec<usize> {
+    let mut lines: Vec<_> = objects
+      .iter()
+      .flat_map(|&o| self.orbit_lines[o].iter().cloned())
+      .collect();
+    lines.sort_unstable();
+    lines.dedup();
+    lines
+  }
+
+  /// Number of objects.
+  pub fn len(&self) -> usize {
+    self.names.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.names.is_empty()
+  }
+
+  pub fn id(&self, name: &str) -> Option<Id> {
+    self.ids.get(name).cloned()
+  }
+
+  pub fn name(&self, id: Id) -> &str {
+    &self.names[id]
+  }
+
+  /// Object `id` directly orbits.
+  pub fn parent(&self, id: Id) -> Option<Id> {
+    self.parents[id]
+  }
+
+  /// Number of objects `id` directly and indirectly orbits.
+  pub fn depth(&self, id: Id) -> usize {
+    self.depths[id]
+  }
+
+  /// Total number of direct and indirect orbits.
+  pub fn total_orbits(&self) -> usize {
+    self.depths.iter().sum()
+  }
+
+  /// Objects `id` directly and indirectly orbits, from the closest one.
+  pub fn ancestors(&self, id: Id) -> impl Iterator<Item = Id> + '_ {
+    std::iter::successors(self.parent(id), move |&id| self.parent(id))
+  }
+
+  /// Deepest object both `a` and `b` are or orbit, if they’re part of the same tree.
+  pub fn lowest_common_ancestor(&self, mut a: Id, mut b: Id) -> Option<Id> {
+    while self.depth(a) > self.depth(b) {
+      a = self.parent(a)?;
+    }
+
+    while self.depth(b) > self.depth(a) {
+      b = self.parent(b)?;
+    }
+
+    while a != b {
+      a = self.parent(a)?;
+      b = self.parent(b)?;
+    }
+
+    Some(a)
+  }
+
+  /// Number of orbits between `a` and `b`.
+  pub fn distance(&self, a: Id, b: Id) -> Option<usize> {
+    let ancestor = self.lowest_common_ancestor(a, b)?;
+    Some(self.depth(a) + self.depth(b) - 2 * self.depth(ancestor))
+  }
+
+  /// Objects from `a` to `b`, both included, through their lowest common ancestor.
+  pub fn path(&self, a: Id, b: Id) -> Option<Vec<Id>> {
+    let ancestor = self.lowest_common_ancestor(a, b)?;
+    let up = |from| {
+      std::iter::once(from)
+        .chain(self.ancestors(from))
+        .take_while(move |&id| id != ancestor)
+    };
+
+    let mut path: Vec<_> = up(a).collect();
+    path.push(ancestor);
+    let down: Vec<_> = up(b).collect();
+    path.extend(down.into_iter().rev());
+
+    Some(path)
+  }
+
+  /// Number of orbital transfers to move `a` to the object `b` orbits.
+  pub fn transfers(&self, a: Id, b: Id) -> Option<usize> {
+    self.distance(self.parent(a)?, self.parent(b)?)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const MAP: &str = "COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\nK)YOU\nI)SAN\n";
+
+  #[test]
+  fn queries() {
+    let tree = OrbitTree::parse(MAP).unwrap();
+    let id = |name| tree.id(name).unwrap();
+
+    assert_eq!(tree.len(), 14);
+    assert_eq!(tree.depth(id("COM")), 0);
+    assert_eq!(tree.depth(id("L")), 7);
+    assert_eq!(
+      tree
+        .ancestors(id("D"))
+        .map(|id| tree.name(id))
+        .collect::<Vec<_>>(),
+      vec!["C", "B", "COM"]
+    );
+
+    assert_eq!(
+      tree.lowest_common_ancestor(id("YOU"), id("SAN")),
+      Some(id("D"))
+    );
+    assert_eq!(tree.lowest_common_ancestor(id("H"), id("F")), Some(id("B")));
+    assert_eq!(tree.lowest_common_ancestor(id("L"), id("E")), Some(id("E")));
+    assert_eq!(tree.distance(id("H"), id("F")), Some(6));
+    assert_eq!(tree.transfers(id("YOU"), id("SAN")), Some(4));
+    assert_eq!(tree.transfers(id("COM"), id("SAN")), None);
+
+    let path = |a, b| {
+      tree
+        .path(id(a), id(b))
+        .unwrap()
+        .into_iter()
+        .map(|id| tree.name(id))
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(
+      path("YOU", "SAN"),
+      vec!["YOU", "K", "J", "E", "D", "I", "SAN"]
+    );
+    assert_eq!(path("E", "L"), vec!["E", "J", "K", "L"]);
+    assert_eq!(path("B", "B"), vec!["B"]);
+  }
+
+  #[test]
+  fn validation() {
+    let problems = |input| match OrbitTree::parse(input) {
+      Err(OrbitError::Invalid(problems)) => problems,
+      other => panic!("unexpected {:?}", other.map(|tree| tree.len())),
+    };
+
+    assert_eq!(
+      problems("A)B\nC)B\nA)C"),
+      vec![Problem::TwoParents {
+        object: "B".to_owned(),
+        lines: vec![1, 2]
+      }]
+    );
+
+    assert_eq!(
+      problems("COM)A\n\nA)B\nX)Y"),
+      vec![Problem::MultipleRoots {
+        roots: vec!["COM".to_owned(), "X".to_owned()],
+        lines: vec![1, 4]
+      }]
+    );
+
+    // B, C and D orbit each other, E and F orbit that loop
+    assert_eq!(
+      problems("COM)A\nB)C\nC)D\nD)B\nD)E\nE)F"),
+      vec![
+        Problem::Cycle {
+          objects: vec!["B".to_owned(), "D".to_owned(), "C".to_owned()],
+          lines: vec![2, 3, 4]
+        },
+        Problem::Unreachable {
+          objects: vec!["E".to_owned(), "F".to_owned()],
+          lines: vec![5, 6]
+        },
+      ]
+    );
+
+    assert_eq!(
+      OrbitTree::parse("A)A").unwrap_err().to_string(),
+      "invalid orbits: cycle through A (lines 1)"
+    );
+    assert!(matches!(
+      OrbitTree::parse("COM)B\nB-C"),
+      Err(OrbitError::Syntax(parse::Error { line: 2, .. }))
+    ));
+  }
+}
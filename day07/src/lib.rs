@@ -1,9 +1,8 @@
-use intcode::{Program, Suspended};
+use intcode::{Program, Suspended, Word};
+use solution::{Answer, Solution};
 
-const INPUT: &str = include_str!("../input.txt");
-
-fn main() {
-  // generate all possible combinations of phases
+/// Generate all possible combinations of phases.
+fn phases_combinations() -> Vec<[Word; 5]> {
   let mut phases_combinations = Vec::new();
 
   for phase1 in 0..=4 {
@@ -36,30 +35,39 @@ fn main() {
     }
   }
 
-  let original_program = Program::from_str(INPUT.trim()).unwrap();
+  phases_combinations
+}
+
+fn part_1(input: &str) -> Result<Word, String> {
+  let original_program = Program::from_str(input.trim())?;
   let mut program = Program::new(original_program.mem_size());
   let mut thrusters_signal = 0;
 
-  for phases in &phases_combinations {
+  for phases in &phases_combinations() {
     let mut signal = 0;
 
-    for amp in 0..=4 {
+    for &phase in phases {
       program.mimick(&original_program);
-      signal = program.run(&[phases[amp], signal]).unwrap().unwrap();
+      signal = program
+        .run(&[phase, signal])?
+        .ok_or_else(|| "amplifier without output".to_owned())?;
     }
 
     thrusters_signal = thrusters_signal.max(signal);
   }
 
-  println!("1st answer: {} thrusters signal", thrusters_signal);
+  Ok(thrusters_signal)
+}
 
-  thrusters_signal = 0;
+fn part_2(input: &str) -> Result<Word, String> {
+  let original_program = Program::from_str(input.trim())?;
+  let mut thrusters_signal = 0;
 
   let mut acses: Vec<_> = (0..=4)
     .map(|_| Program::new(original_program.mem_size()))
     .collect();
 
-  for mut phases in phases_combinations {
+  for mut phases in phases_combinations() {
     for phase in &mut phases {
       *phase += 5;
     }
@@ -71,13 +79,14 @@ fn main() {
       acs.mimick(&original_program);
     }
 
-    let mut suspended: Vec<_> = (0..=4)
-      .map(|amp| {
-        let suspended = acses[amp].run_suspended(&[phases[amp], signal]).unwrap();
-        signal = suspended.output().unwrap();
-        suspended
-      })
-      .collect();
+    let mut suspended = Vec::with_capacity(5);
+    for (acs, &phase) in acses.iter_mut().zip(&phases) {
+      let s = acs.run_suspended(&[phase, signal])?;
+      signal = s
+        .output()
+        .ok_or_else(|| "amplifier without output".to_owned())?;
+      suspended.push(s);
+    }
 
     // sets input / outputs
     for amp in 0..=4 {
@@ -94,8 +103,10 @@ fn main() {
     loop {
       for amp in 0..=4 {
         // run the ACS
-        suspended[amp] = acses[amp].rerun(suspended[amp].clone()).unwrap();
-        let output = suspended[amp].output().unwrap();
+        suspended[amp] = acses[amp].rerun(suspended[amp].clone())?;
+        let output = suspended[amp]
+          .output()
+          .ok_or_else(|| "amplifier without output".to_owned())?;
 
         // provide the ACS output as input for the next ACS
         if let Suspended::Running { ref mut inputs, .. } = suspended[(amp + 1) % 5] {
@@ -114,5 +125,21 @@ fn main() {
     }
   }
 
-  println!("2nd answer: {} thrusters signal", thrusters_signal);
+  Ok(thrusters_signal)
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+  fn day(&self) -> u8 {
+    7
+  }
+
+  fn part_1(&self, input: &str) -> Result<Answer, String> {
+    Ok(part_1(input)?.into())
+  }
+
+  fn part_2(&self, input: &str) -> Result<Answer, String> {
+    Ok(part_2(input)?.into())
+  }
 }
@@ -0,0 +1,110 @@
+use grid::ocr;
+use grid::render::{Canvas, Palette, Swatch};
+use grid::DenseGrid;
+use solution::{Answer, Solution};
+
+const WIDTH: usize = 25;
+const HEIGHT: usize = 6;
+
+/// Split the encoded image into its layers, from top to bottom.
+fn read_image(input: &str, width: usize, height: usize) -> parse::Result<Vec<DenseGrid<u8>>> {
+  let line = parse::lines(input).next().ok_or_else(|| parse::Error {
+    line: 1,
+    column: 1,
+    message: "missing image".to_owned(),
+  })?;
+  let pixels = line.text();
+
+  if let Some(i) = pixels.find(|c| !('0'..='2').contains(&c)) {
+    return Err(line.error(&pixels[i..], "expected a pixel (0, 1 or 2)"));
+  }
+
+  if pixels.len() % (width * height) != 0 {
+    return Err(line.error(
+      &pixels[pixels.len()..],
+      format!("expected {}×{} layers", width, height),
+    ));
+  }
+
+  Ok(
+    pixels
+      .as_bytes()
+      .chunks(width * height)
+      .map(|chunk| DenseGrid::from_cells(width, height, chunk.to_vec()).unwrap())
+      .collect(),
+  )
+}
+
+fn part1(image: &[DenseGrid<u8>]) -> usize {
+  let (layer, _) = image
+    .iter()
+    .enumerate()
+    .fold((0, usize::MAX), |(layer, zeros), (i, pixels)| {
+      let count = pixels.cells().iter().filter(|&&p| p == b'0').count();
+      if count < zeros {
+        (i, count)
+      } else {
+        (layer, zeros)
+      }
+    });
+
+  let (ones, twos) = image[layer]
+    .cells()
+    .iter()
+    .fold((0, 0), |(ones, twos), &p| {
+      if p == b'1' {
+        (ones + 1, twos)
+      } else if p == b'2' {
+        (ones, twos + 1)
+      } else {
+        (ones, twos)
+      }
+    });
+
+  ones * twos
+}
+
+/// Stack the layers; `2` is transparent.
+fn part2(image: &[DenseGrid<u8>], width: usize, height: usize) -> DenseGrid<u8> {
+  let mut framebuffer = DenseGrid::new(width, height, b'2');
+
+  for layer in image.iter().rev() {
+    for (p, &dst) in layer.iter() {
+      if dst != b'2' {
+        framebuffer[p] = dst;
+      }
+    }
+  }
+
+  framebuffer
+}
+
+/// Black (`0`) and transparent pixels are left blank, white (`1`) ones are filled.
+fn palette() -> Palette<u8> {
+  Palette::new(Swatch::new(' ', [0, 0, 0])).with(b'1', Swatch::new('█', [255, 255, 255]))
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+  fn day(&self) -> u8 {
+    8
+  }
+
+  fn part_1(&self, input: &str) -> Result<Answer, String> {
+    let image = read_image(input, WIDTH, HEIGHT)?;
+    Ok(part1(&image).into())
+  }
+
+  fn part_2(&self, input: &str) -> Result<Answer, String> {
+    let image = read_image(input, WIDTH, HEIGHT)?;
+    let blended = part2(&image, WIDTH, HEIGHT);
+    let white = blended.iter().filter(|&(_, &c)| c == b'1').map(|(p, _)| p);
+
+    Ok(
+      ocr::read(white)
+        .map(Answer::Text)
+        .unwrap_or_else(|_| Answer::Image(Canvas::new(blended.iter()).text(&palette()))),
+    )
+  }
+}
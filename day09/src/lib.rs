@@ -0,0 +1,25 @@
+use intcode::{Program, Word};
+use solution::{Answer, Solution};
+
+fn boost(input: &str, mode: Word) -> Result<Option<Word>, String> {
+  let mut program = Program::from_str(input.trim())?;
+  program.run(&[mode])
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+  fn day(&self) -> u8 {
+    9
+  }
+
+  // test mode
+  fn part_1(&self, input: &str) -> Result<Answer, String> {
+    Ok(boost(input, 1)?.into())
+  }
+
+  // sensor boost mode
+  fn part_2(&self, input: &str) -> Result<Answer, String> {
+    Ok(boost(input, 2)?.into())
+  }
+}
@@ -0,0 +1,252 @@
+use grid::{DenseGrid, Point};
+use solution::{Answer, Solution};
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum MapCell {
+  Empty,
+  Asteroid,
+}
+
+type SpaceMap = DenseGrid<MapCell>;
+
+fn get_map(input: &str) -> parse::Result<SpaceMap> {
+  DenseGrid::parse(input, |c| match c {
+    '.' => Some(MapCell::Empty),
+    '#' => Some(MapCell::Asteroid),
+    _ => None,
+  })
+}
+
+fn asteroids(map: &SpaceMap) -> impl Iterator<Item = Point> + '_ {
+  map
+    .iter()
+    .filter(|(_, &cell)| cell == MapCell::Asteroid)
+    .map(|(p, _)| p)
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+  if b == 0 {
+    a.abs()
+  } else {
+    gcd(b, a % b)
+  }
+}
+
+/// Smallest step going from `from` towards `to`, the same for all points on that line of sight.
+fn direction(from: Point, to: Point) -> Point {
+  let v = to - from;
+  let d = gcd(v.x, v.y).max(1);
+  Point::new(v.x / d, v.y / d)
+}
+
+/// Clockwise angle of a direction from up, in `[0, 2π)`; `y` points down.
+fn angle(dir: Point) -> f64 {
+  let angle = (dir.x as f64).atan2(-dir.y as f64);
+
+  if angle < 0. {
+    angle + 2. * PI
+  } else {
+    angle
+  }
+}
+
+/// Asteroids seen from `center`, by direction, nearest first.
+fn lines_of_sight(map: &SpaceMap, center: Point) -> HashMap<Point, Vec<Point>> {
+  let mut lines: HashMap<_, Vec<_>> = HashMap::new();
+
+  for p in asteroids(map).filter(|&p| p != center) {
+    lines.entry(direction(center, p)).or_default().push(p);
+  }
+
+  for aligned in lines.values_mut() {
+    aligned.sort_unstable_by_key(|&p| p.manhattan(center));
+  }
+
+  lines
+}
+
+/// Asteroid seeing the most other asteroids, along with the nearest asteroid in each direction.
+fn part_1(map: &SpaceMap) -> Option<(Point, HashMap<Point, Point>)> {
+  asteroids(map)
+    .map(|center| {
+      let nearests: HashMap<_, _> = lines_of_sight(map, center)
+        .into_iter()
+        .map(|(dir, aligned)| (dir, aligned[0]))
+        .collect();
+      (center, nearests)
+    })
+    .max_by_key(|(_, nearests)| nearests.len())
+}
+
+/// 200th asteroid vaporized by a laser turning clockwise from up, one asteroid per direction and per
+/// turn.
+fn part_2(map: &SpaceMap, center: Point) -> Option<Point> {
+  let mut lines: Vec<_> = lines_of_sight(map, center).into_iter().collect();
+  lines.sort_by(|(a, _), (b, _)| angle(*a).partial_cmp(&angle(*b)).unwrap());
+
+  // asteroids in the order they’re vaporized: by turn, and then by angle
+  let mut order: Vec<_> = lines
+    .iter()
+    .enumerate()
+    .flat_map(|(i, (_, aligned))| {
+      aligned
+        .iter()
+        .enumerate()
+        .map(move |(turn, &p)| (turn, i, p))
+    })
+    .collect();
+  order.sort_unstable();
+
+  order.get(199).map(|&(_, _, p)| p)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+  fn day(&self) -> u8 {
+    10
+  }
+
+  fn part_1(&self, input: &str) -> Result<Answer, String> {
+    let map = get_map(input)?;
+    let seen = part_1(&map).map(|(_, nearests)| nearests.len());
+    Ok(seen.into())
+  }
+
+  fn part_2(&self, input: &str) -> Result<Answer, String> {
+    let map = get_map(input)?;
+    let (station, _) =
+      part_1(&map).ok_or_else(|| "no asteroid to build a station on".to_owned())?;
+    let p = part_2(&map, station);
+    Ok(p.map(|p| p.x * 100 + p.y).into())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_1() {
+    const TEST_INPUT: &str = r#".#..#
+.....
+#####
+....#
+...##"#;
+
+    let map = get_map(TEST_INPUT).unwrap();
+    let p = part_1(&map).unwrap();
+    eprintln!("{:?}", p.0);
+
+    assert_eq!(p.1.len(), 8);
+  }
+
+  #[test]
+  fn test_2() {
+    const TEST_INPUT: &str = r#".#..##.###...#######
+##.############..##.
+.#.######.########.#
+.###.#######.####.#.
+#####.##.#.##.###.##
+..#####..#.#########
+####################
+#.####....###.#.#.##
+##.#################
+#####.##.###..####..
+..######..##.#######
+####.##.####...##..#
+.#####..#.######.###
+##...#.##########...
+#.##########.#######
+.####.#.###.###.#.##
+....##.##.###..#####
+.#.#.###########.###
+#.#.#.#####.####.###
+###.##.####.##.#..##"#;
+
+    let map = get_map(TEST_INPUT).unwrap();
+    let p = part_1(&map).unwrap();
+    eprintln!("{:?}", p.0);
+
+    assert_eq!(p.1.len(), 210);
+  }
+
+  #[test]
+  fn vaporized_1() {
+    const TEST_INPUT: &str = r#".#..##.###...#######
+##.############..##.
+.#.######.########.#
+.###.#######.####.#.
+#####.##.#.##.###.##
+..#####..#.#########
+####################
+#.####....###.#.#.##
+##.#################
+#####.##.###..####..
+..######..##.#######
+####.##.####...##..#
+.#####..#.######.###
+##...#.##########...
+#.##########.#######
+.####.#.###.###.#.##
+....##.##.###..#####
+.#.#.###########.###
+#.#.#.#####.####.###
+###.##.####.##.#..##"#;
+
+    let map = get_map(TEST_INPUT).unwrap();
+    let p = part_1(&map).unwrap();
+    let q = part_2(&map, p.0).unwrap();
+    assert_eq!(q.x * 100 + q.y, 802);
+  }
+
+  #[test]
+  fn vaporized_2() {
+    const TEST_INPUT: &str = r#".#..##.###...#######
+##.############..##.
+.#.######.########.#
+.###.#######.####.#.
+#####.##.#.##.###.##
+..#####..#.#########
+####################
+#.####....###.#.#.##
+##.#################
+#####.##.###..####..
+..######..##.#######
+####.##.####...##..#
+.#####..#.######.###
+##...#.##########...
+#.##########.#######
+.####.#.###.###.#.##
+....##.##.###..#####
+.#.#.###########.###
+#.#.#.#####.####.###
+###.##.####.##.#..##"#;
+
+    let map = get_map(TEST_INPUT).unwrap();
+    let p = part_1(&map).unwrap();
+    let q = part_2(&map, p.0).unwrap();
+    assert_eq!(q.x * 100 + q.y, 802);
+  }
+
+  #[test]
+  fn angles() {
+    let up = Point::new(0, -1);
+    assert_eq!(angle(up), 0.);
+    assert_eq!(angle(Point::new(1, -1)), PI / 4.);
+    assert_eq!(angle(Point::new(1, 0)), PI / 2.);
+    assert_eq!(angle(Point::new(0, 1)), PI);
+    assert_eq!(angle(Point::new(-1, 0)), 3. * PI / 2.);
+
+    assert_eq!(
+      direction(Point::new(1, 1), Point::new(7, -8)),
+      Point::new(2, -3)
+    );
+    assert_eq!(
+      direction(Point::new(1, 1), Point::new(1, 5)),
+      Point::new(0, 1)
+    );
+  }
+}
@@ -0,0 +1,98 @@
+use grid::ocr;
+use grid::render::{Canvas, Palette, Swatch};
+use grid::{Direction, Point, SparseGrid};
+use intcode::replay::Replay;
+use intcode::{Program, Word};
+use solution::{Answer, Solution};
+use std::path::Path;
+
+fn part_1(program: &mut Program, starting_panel: Word) -> Result<SparseGrid<Word>, String> {
+  let mut grid = SparseGrid::new();
+  let mut dir = Direction::Up;
+  let mut robot_pos = Point::ORIGIN;
+
+  loop {
+    let suspended =
+      program.run_suspended(&[grid.get(robot_pos).cloned().unwrap_or(starting_panel)])?;
+
+    let color = match suspended.output() {
+      Some(color) => color,
+      None => break,
+    };
+
+    grid.insert(robot_pos, color);
+
+    let suspended = program.run_suspended(&[])?;
+    dir = match suspended.output() {
+      Some(0) => dir.turn_left(),
+      Some(1) => dir.turn_right(),
+      turn => return Err(format!("wrong turn: {:?}", turn)),
+    };
+    robot_pos = robot_pos.step(dir);
+  }
+
+  Ok(grid)
+}
+
+/// Panels are black unless painted white.
+fn palette() -> Palette<Word> {
+  Palette::new(Swatch::new(' ', [0, 0, 0])).with(1, Swatch::new('█', [255, 255, 255]))
+}
+
+fn render(grid: &SparseGrid<Word>) -> String {
+  Canvas::new(grid.iter()).text(&palette())
+}
+
+/// Read the registration identifier, falling back to its picture if it cannot be read.
+fn identifier(grid: &SparseGrid<Word>) -> Answer {
+  let white = grid.iter().filter(|&(_, &c)| c == 1).map(|(p, _)| p);
+
+  ocr::read(white)
+    .map(Answer::Text)
+    .unwrap_or_else(|_| Answer::Image(render(grid)))
+}
+
+/// Paint the registration identifier while recording the session of the robot.
+pub fn record<P>(input: &str, path: P) -> Result<Answer, String>
+where
+  P: AsRef<Path>,
+{
+  let mut program = Program::from_str(input.trim())?;
+  program.start_recording();
+  let grid = part_1(&mut program, 1)?;
+  program.take_recording().unwrap().save(path)?;
+
+  Ok(identifier(&grid))
+}
+
+/// Check that the robot still paints the registration identifier as recorded.
+pub fn replay<P>(input: &str, path: P) -> Result<String, String>
+where
+  P: AsRef<Path>,
+{
+  let mut program = Program::from_str(input.trim())?;
+  let replay = Replay::load(path)?;
+
+  replay
+    .verify(&mut program)
+    .map(|_| format!("{} events", replay.events().len()))
+    .map_err(|divergence| divergence.to_string())
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+  fn day(&self) -> u8 {
+    11
+  }
+
+  fn part_1(&self, input: &str) -> Result<Answer, String> {
+    let mut program = Program::from_str(input.trim())?;
+    Ok(part_1(&mut program, 0)?.len().into())
+  }
+
+  fn part_2(&self, input: &str) -> Result<Answer, String> {
+    let mut program = Program::from_str(input.trim())?;
+    Ok(identifier(&part_1(&mut program, 1)?))
+  }
+}
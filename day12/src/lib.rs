@@ -1,6 +1,6 @@
 use num::Integer;
+use solution::{Answer, Solution};
 
-const INPUT: &str = include_str!("../input.txt");
 const INITIAL_VELOCITY: [i64; 3] = [0, 0, 0];
 
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
@@ -11,11 +11,11 @@ struct Moon {
 
 impl Moon {
   fn pot(&self) -> u64 {
-    self.pos.iter().copied().map(|x| x.abs() as u64).sum()
+    self.pos.iter().copied().map(i64::unsigned_abs).sum()
   }
 
   fn kin(&self) -> u64 {
-    self.vel.iter().copied().map(|x| x.abs() as u64).sum()
+    self.vel.iter().copied().map(i64::unsigned_abs).sum()
   }
 
   fn tot(&self) -> u64 {
@@ -23,22 +23,39 @@ impl Moon {
   }
 }
 
-fn get_moons(input: &str) -> Vec<Moon> {
-  input
-    .lines()
-    .map(|line| {
-      let xyz = line.trim()[1..line.len() - 1]
-        .split(',')
-        .map(|coord| coord.trim()[2..].parse())
-        .collect::<Result<Vec<_>, _>>()
-        .unwrap();
-
-      Moon {
-        pos: [xyz[0], xyz[1], xyz[2]],
-        vel: INITIAL_VELOCITY,
+/// Parse the moons, one `<x=.., y=.., z=..>` position per line.
+fn get_moons(input: &str) -> parse::Result<Vec<Moon>> {
+  let moons = parse::records(input, |line| {
+    let coords = line.key_values('<', '>')?;
+    let mut pos = [0; 3];
+
+    if coords.len() != pos.len() {
+      return Err(line.error(line.text(), "expected x, y and z coordinates"));
+    }
+
+    for ((&(key, value), expected), p) in coords.iter().zip(&["x", "y", "z"]).zip(&mut pos) {
+      if key != *expected {
+        return Err(line.error(key, format!("expected {}, found {}", expected, key)));
       }
+
+      *p = value;
+    }
+
+    Ok(Moon {
+      pos,
+      vel: INITIAL_VELOCITY,
     })
-    .collect()
+  })?;
+
+  if moons.is_empty() {
+    return Err(parse::Error {
+      line: 1,
+      column: 1,
+      message: "no moon".to_owned(),
+    });
+  }
+
+  Ok(moons)
 }
 
 fn update_vel(moons: &mut [Moon]) {
@@ -166,19 +183,25 @@ fn find_cycle(moons: &mut [Moon]) -> usize {
   }
 
   let lcm_xy = found_x.unwrap().lcm(&found_y.unwrap());
-  let lcm_z = lcm_xy.lcm(&found_z.unwrap());
-
-  lcm_z
+  lcm_xy.lcm(&found_z.unwrap())
 }
 
-fn main() {
-  let mut moons = get_moons(INPUT);
-  let energy = simulate(&mut moons, 1000);
-  println!("part 1: {}", energy);
+pub struct Day12;
 
-  let mut moons = get_moons(INPUT);
-  let cycle = find_cycle(&mut moons);
-  println!("part 2: {}", cycle);
+impl Solution for Day12 {
+  fn day(&self) -> u8 {
+    12
+  }
+
+  fn part_1(&self, input: &str) -> Result<Answer, String> {
+    let mut moons = get_moons(input)?;
+    Ok(simulate(&mut moons, 1000).into())
+  }
+
+  fn part_2(&self, input: &str) -> Result<Answer, String> {
+    let mut moons = get_moons(input)?;
+    Ok(find_cycle(&mut moons).into())
+  }
 }
 
 #[cfg(test)]
@@ -191,9 +214,22 @@ mod tests {
 <x=2, y=-10, z=-7>
 <x=4, y=-8, z=8>
 <x=3, y=5, z=-1>"#;
-    let mut moons = get_moons(INPUT_TEST);
+    let mut moons = get_moons(INPUT_TEST).unwrap();
 
     let energy = simulate(&mut moons, 10);
     assert_eq!(energy, 179);
   }
+
+  #[test]
+  fn parse_errors() {
+    assert_eq!(
+      get_moons("<x=1, y=2, z=3>\n<x=1, z=2, y=3>")
+        .unwrap_err()
+        .line,
+      2
+    );
+    assert_eq!(get_moons("<x=1, y=2, z=a>").unwrap_err().column, 14);
+    assert!(get_moons("<x=1, y=2>").is_err());
+    assert!(get_moons("").is_err());
+  }
 }
@@ -0,0 +1,123 @@
+use grid::{Point, SparseGrid};
+use intcode::replay::Replay;
+use intcode::{Program, Word};
+use solution::{Answer, Solution};
+use std::path::Path;
+
+/// Read the rest of a tile, which the game must output.
+fn tile_output(program: &mut Program) -> Result<Word, String> {
+  program
+    .run_suspended(&[])?
+    .output()
+    .ok_or_else(|| "incomplete tile".to_owned())
+}
+
+fn part_1(input: &str) -> Result<SparseGrid<Word>, String> {
+  let mut program = Program::from_str(input.trim())?;
+  let mut tiles = SparseGrid::new();
+
+  loop {
+    let x = if let Some(output) = program.run_suspended(&[])?.output() {
+      output
+    } else {
+      break Ok(tiles);
+    };
+
+    let y = tile_output(&mut program)?;
+    let tile = tile_output(&mut program)?;
+    tiles.insert(Point::new(x, y), tile);
+  }
+}
+
+/// Load the arcade with quarters inserted so that the game can be played.
+fn arcade(input: &str) -> Result<Program, String> {
+  let mut program = Program::from_str(input.trim())?;
+  program.write(0, 2)?;
+  Ok(program)
+}
+
+fn part_2(program: &mut Program) -> Result<Word, String> {
+  let mut paddle = None;
+  let mut ball = None;
+  let mut next_input = 0;
+  let mut score = 0;
+
+  loop {
+    let x = if let Some(output) = program.run_suspended(&[next_input])?.output() {
+      output
+    } else {
+      break Ok(score);
+    };
+
+    next_input = 0;
+
+    let y = tile_output(program)?;
+    let tile = tile_output(program)?;
+
+    if x == -1 && y == 0 {
+      score = tile;
+    }
+
+    // update paddle
+    match tile {
+      3 => paddle = Some(Point::new(x, y)),
+      4 => ball = Some(Point::new(x, y)),
+      _ => (),
+    }
+
+    if let (Some(paddle), Some(ball)) = (paddle, ball) {
+      if paddle.x < ball.x {
+        next_input = 1;
+      } else if paddle.x > ball.x {
+        next_input = -1;
+      }
+    }
+  }
+}
+
+/// Play the game while recording the session of the paddle AI.
+pub fn record<P>(input: &str, path: P) -> Result<Answer, String>
+where
+  P: AsRef<Path>,
+{
+  let mut program = arcade(input)?;
+  program.start_recording();
+  let score = part_2(&mut program)?;
+  program.take_recording().unwrap().save(path)?;
+
+  Ok(score.into())
+}
+
+/// Check that the paddle AI still plays the same game as recorded.
+pub fn replay<P>(input: &str, path: P) -> Result<String, String>
+where
+  P: AsRef<Path>,
+{
+  let mut program = arcade(input)?;
+  let replay = Replay::load(path)?;
+
+  replay
+    .verify(&mut program)
+    .map(|_| format!("{} events", replay.events().len()))
+    .map_err(|divergence| divergence.to_string())
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+  fn day(&self) -> u8 {
+    13
+  }
+
+  fn part_1(&self, input: &str) -> Result<Answer, String> {
+    let blocks = part_1(input)?
+      .into_iter()
+      .filter(|(_, tile)| *tile == 2)
+      .count();
+    Ok(blocks.into())
+  }
+
+  fn part_2(&self, input: &str) -> Result<Answer, String> {
+    Ok(part_2(&mut arcade(input)?)?.into())
+  }
+}
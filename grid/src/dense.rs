@@ -0,0 +1,191 @@
+use crate::render::Canvas;
+use crate::{BoundingBox, Point};
+use std::ops::{Index, IndexMut};
+
+/// Rectangular grid storing every cell, with `(0, 0)` at the top-left corner.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct DenseGrid<T> {
+  width: usize,
+  height: usize,
+  cells: Vec<T>,
+}
+
+impl<T> DenseGrid<T> {
+  /// Grid with all cells set to `value`.
+  pub fn new(width: usize, height: usize, value: T) -> Self
+  where
+    T: Clone,
+  {
+    DenseGrid {
+      width,
+      height,
+      cells: vec![value; width * height],
+    }
+  }
+
+  /// Grid made of cells stored row by row.
+  pub fn from_cells(width: usize, height: usize, cells: Vec<T>) -> Result<Self, String> {
+    if cells.len() != width * height {
+      return Err(format!(
+        "{} cells cannot make a {}×{} grid",
+        cells.len(),
+        width,
+        height
+      ));
+    }
+
+    Ok(DenseGrid {
+      width,
+      height,
+      cells,
+    })
+  }
+
+  /// Parse a grid of characters, one line per row; `cell` rejects unexpected characters.
+  pub fn parse<F>(input: &str, cell: F) -> parse::Result<Self>
+  where
+    F: FnMut(char) -> Option<T>,
+  {
+    let (width, height, cells) = parse::grid(input, cell)?;
+
+    Ok(DenseGrid {
+      width,
+      height,
+      cells,
+    })
+  }
+
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  pub fn height(&self) -> usize {
+    self.height
+  }
+
+  pub fn bounding_box(&self) -> Option<BoundingBox> {
+    if self.cells.is_empty() {
+      None
+    } else {
+      Some(BoundingBox {
+        min: Point::ORIGIN,
+        max: Point::new(self.width as i64 - 1, self.height as i64 - 1),
+      })
+    }
+  }
+
+  fn offset(&self, p: Point) -> Option<usize> {
+    if p.x < 0 || p.y < 0 || p.x as usize >= self.width || p.y as usize >= self.height {
+      None
+    } else {
+      Some(p.x as usize + p.y as usize * self.width)
+    }
+  }
+
+  fn point(&self, offset: usize) -> Point {
+    Point::new((offset % self.width) as i64, (offset / self.width) as i64)
+  }
+
+  pub fn contains(&self, p: Point) -> bool {
+    self.offset(p).is_some()
+  }
+
+  pub fn get(&self, p: Point) -> Option<&T> {
+    self.offset(p).map(|offset| &self.cells[offset])
+  }
+
+  pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+    self.offset(p).map(move |offset| &mut self.cells[offset])
+  }
+
+  /// Cells, row by row.
+  pub fn cells(&self) -> &[T] {
+    &self.cells
+  }
+
+  /// Cells with their position, row by row.
+  pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+    self
+      .cells
+      .iter()
+      .enumerate()
+      .map(move |(offset, cell)| (self.point(offset), cell))
+  }
+
+  pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+    self.cells.chunks(self.width.max(1))
+  }
+
+  /// Orthogonal neighbours of `p` that lie in the grid.
+  pub fn neighbours(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+    p.neighbours().filter(move |&q| self.contains(q))
+  }
+
+  /// Apply a function to every cell.
+  pub fn map<F, U>(&self, f: F) -> DenseGrid<U>
+  where
+    F: FnMut(&T) -> U,
+  {
+    DenseGrid {
+      width: self.width,
+      height: self.height,
+      cells: self.cells.iter().map(f).collect(),
+    }
+  }
+
+  /// Render the grid as text, one line per row.
+  pub fn render<F>(&self, mut f: F) -> String
+  where
+    F: FnMut(&T) -> char,
+  {
+    // every cell of a dense grid is set
+    Canvas::new(self.iter()).glyphs(|cell| cell.map_or(' ', &mut f))
+  }
+}
+
+impl<T> Index<Point> for DenseGrid<T> {
+  type Output = T;
+
+  fn index(&self, p: Point) -> &Self::Output {
+    self
+      .get(p)
+      .unwrap_or_else(|| panic!("{} is out of the grid", p))
+  }
+}
+
+impl<T> IndexMut<Point> for DenseGrid<T> {
+  fn index_mut(&mut self, p: Point) -> &mut Self::Output {
+    self
+      .get_mut(p)
+      .unwrap_or_else(|| panic!("{} is out of the grid", p))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_and_render() {
+    let grid = DenseGrid::parse(".#.\n##.\n", |c| Some(c == '#')).unwrap();
+
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert!(grid[Point::new(1, 0)]);
+    assert!(!grid[Point::new(2, 1)]);
+    assert_eq!(grid.get(Point::new(3, 0)), None);
+    assert_eq!(grid.iter().filter(|(_, &c)| c).count(), 3);
+    assert_eq!(grid.render(|&c| if c { '█' } else { ' ' }), " █ \n██ \n");
+
+    assert!(DenseGrid::parse(".#.\n#.\n", |c| Some(c == '#')).is_err());
+    assert!(DenseGrid::from_cells(2, 2, vec![0; 3]).is_err());
+  }
+
+  #[test]
+  fn neighbours() {
+    let grid = DenseGrid::new(2, 2, 0);
+    let mut neighbours: Vec<_> = grid.neighbours(Point::ORIGIN).collect();
+    neighbours.sort();
+
+    assert_eq!(neighbours, vec![Point::new(0, 1), Point::new(1, 0)]);
+  }
+}
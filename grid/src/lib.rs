@@ -0,0 +1,250 @@
+//! 2D grids and geometry shared by the grid-based days.
+//!
+//! Coordinates follow the screen convention: `x` grows to the right and `y` grows downwards, so
+//! that going [`Direction::Up`] decreases `y`.
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Sub};
+
+mod dense;
+pub mod ocr;
+pub mod render;
+mod sparse;
+
+pub use crate::dense::DenseGrid;
+pub use crate::sparse::SparseGrid;
+
+/// A point on the 2D signed integer plane.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point {
+  pub x: i64,
+  pub y: i64,
+}
+
+impl Point {
+  pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+  pub const fn new(x: i64, y: i64) -> Self {
+    Point { x, y }
+  }
+
+  /// Manhattan (taxicab) distance between two points.
+  pub fn manhattan(self, other: Point) -> u64 {
+    (self.x - other.x).unsigned_abs() + (self.y - other.y).unsigned_abs()
+  }
+
+  /// Point one step away in the given direction.
+  pub fn step(self, dir: Direction) -> Self {
+    self + dir.delta()
+  }
+
+  /// The four orthogonal neighbours, in the order of [`Direction::ALL`].
+  pub fn neighbours(self) -> impl Iterator<Item = Point> {
+    Direction::ALL.iter().map(move |&dir| self.step(dir))
+  }
+
+  /// The eight orthogonal and diagonal neighbours.
+  pub fn neighbours_diagonal(self) -> impl Iterator<Item = Point> {
+    (-1..=1)
+      .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+      .filter(|&d| d != (0, 0))
+      .map(move |(dx, dy)| Point::new(self.x + dx, self.y + dy))
+  }
+}
+
+impl From<[i64; 2]> for Point {
+  fn from([x, y]: [i64; 2]) -> Self {
+    Point { x, y }
+  }
+}
+
+impl Add for Point {
+  type Output = Point;
+
+  fn add(self, rhs: Point) -> Self::Output {
+    Point::new(self.x + rhs.x, self.y + rhs.y)
+  }
+}
+
+impl AddAssign for Point {
+  fn add_assign(&mut self, rhs: Point) {
+    *self = *self + rhs;
+  }
+}
+
+impl Sub for Point {
+  type Output = Point;
+
+  fn sub(self, rhs: Point) -> Self::Output {
+    Point::new(self.x - rhs.x, self.y - rhs.y)
+  }
+}
+
+impl fmt::Display for Point {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "({}, {})", self.x, self.y)
+  }
+}
+
+/// One of the four orthogonal directions.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Direction {
+  Up,
+  Right,
+  Down,
+  Left,
+}
+
+impl Direction {
+  /// All directions, clockwise from up.
+  pub const ALL: [Direction; 4] = [
+    Direction::Up,
+    Direction::Right,
+    Direction::Down,
+    Direction::Left,
+  ];
+
+  /// Parse the `U`, `R`, `D` and `L` letters.
+  pub fn from_letter(letter: char) -> Option<Self> {
+    match letter {
+      'U' => Some(Direction::Up),
+      'R' => Some(Direction::Right),
+      'D' => Some(Direction::Down),
+      'L' => Some(Direction::Left),
+      _ => None,
+    }
+  }
+
+  /// Offset of a single step in that direction.
+  pub fn delta(self) -> Point {
+    match self {
+      Direction::Up => Point::new(0, -1),
+      Direction::Right => Point::new(1, 0),
+      Direction::Down => Point::new(0, 1),
+      Direction::Left => Point::new(-1, 0),
+    }
+  }
+
+  pub fn turn_left(self) -> Self {
+    match self {
+      Direction::Up => Direction::Left,
+      Direction::Right => Direction::Up,
+      Direction::Down => Direction::Right,
+      Direction::Left => Direction::Down,
+    }
+  }
+
+  pub fn turn_right(self) -> Self {
+    match self {
+      Direction::Up => Direction::Right,
+      Direction::Right => Direction::Down,
+      Direction::Down => Direction::Left,
+      Direction::Left => Direction::Up,
+    }
+  }
+
+  pub fn reverse(self) -> Self {
+    self.turn_left().turn_left()
+  }
+}
+
+/// Smallest rectangle containing a set of points; both corners are inclusive.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct BoundingBox {
+  pub min: Point,
+  pub max: Point,
+}
+
+impl BoundingBox {
+  /// Bounding box of a single point.
+  pub fn new(p: Point) -> Self {
+    BoundingBox { min: p, max: p }
+  }
+
+  /// Bounding box of some points, if any.
+  pub fn from_points<I>(points: I) -> Option<Self>
+  where
+    I: IntoIterator<Item = Point>,
+  {
+    let mut points = points.into_iter();
+    let mut bbox = BoundingBox::new(points.next()?);
+
+    for p in points {
+      bbox.extend(p);
+    }
+
+    Some(bbox)
+  }
+
+  /// Grow the box so that it contains `p`.
+  pub fn extend(&mut self, p: Point) {
+    self.min = Point::new(self.min.x.min(p.x), self.min.y.min(p.y));
+    self.max = Point::new(self.max.x.max(p.x), self.max.y.max(p.y));
+  }
+
+  pub fn width(&self) -> u64 {
+    (self.max.x - self.min.x) as u64 + 1
+  }
+
+  pub fn height(&self) -> u64 {
+    (self.max.y - self.min.y) as u64 + 1
+  }
+
+  pub fn contains(&self, p: Point) -> bool {
+    (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+  }
+
+  /// All points of the box, row by row.
+  pub fn points(self) -> impl Iterator<Item = Point> {
+    (self.min.y..=self.max.y)
+      .flat_map(move |y| (self.min.x..=self.max.x).map(move |x| Point::new(x, y)))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn points() {
+    let p = Point::new(3, -4);
+
+    assert_eq!(p.manhattan(Point::ORIGIN), 7);
+    assert_eq!(p.step(Direction::Up), Point::new(3, -5));
+    assert_eq!(p + Point::new(1, 1) - Point::new(4, -3), Point::ORIGIN);
+    assert_eq!(
+      Point::ORIGIN.neighbours().collect::<Vec<_>>(),
+      [[0, -1], [1, 0], [0, 1], [-1, 0]]
+        .iter()
+        .map(|&p| Point::from(p))
+        .collect::<Vec<_>>()
+    );
+    assert_eq!(Point::ORIGIN.neighbours_diagonal().count(), 8);
+  }
+
+  #[test]
+  fn directions() {
+    for &dir in &Direction::ALL {
+      assert_eq!(dir.turn_left().turn_right(), dir);
+      assert_eq!(dir.reverse().delta() + dir.delta(), Point::ORIGIN);
+    }
+
+    assert_eq!(Direction::Up.turn_right(), Direction::Right);
+    assert_eq!(Direction::Up.turn_left(), Direction::Left);
+    assert_eq!(Direction::from_letter('D'), Some(Direction::Down));
+    assert_eq!(Direction::from_letter('x'), None);
+  }
+
+  #[test]
+  fn bounding_box() {
+    let bbox = BoundingBox::from_points(vec![Point::new(1, 2), Point::new(-1, 0)]).unwrap();
+
+    assert_eq!(bbox.min, Point::new(-1, 0));
+    assert_eq!(bbox.max, Point::new(1, 2));
+    assert_eq!((bbox.width(), bbox.height()), (3, 3));
+    assert!(bbox.contains(Point::new(0, 1)));
+    assert!(!bbox.contains(Point::new(2, 1)));
+    assert_eq!(bbox.points().count(), 9);
+    assert_eq!(BoundingBox::from_points(Vec::new()), None);
+  }
+}
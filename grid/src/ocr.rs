@@ -0,0 +1,289 @@
+//! Recognition of the block capital letters drawn by some puzzles.
+//!
+//! Two fonts are known: a small one, 6 pixels high and usually 4 wide, and a large one, 10 pixels
+//! high and 6 wide. Letters are separated by blank columns and are recognized regardless of where
+//! they are drawn.
+
+use crate::{BoundingBox, Point};
+use std::collections::HashSet;
+
+type Glyph = (char, &'static [&'static str]);
+
+const SMALL_FONT: &[Glyph] = &[
+  ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+  ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+  ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+  ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+  ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+  ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+  ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+  ('I', &["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+  ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+  ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+  ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+  ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+  ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+  ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+  ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+  ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+  ('Y', &["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+  ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+const LARGE_FONT: &[Glyph] = &[
+  (
+    'A',
+    &[
+      "..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#",
+      "#....#",
+    ],
+  ),
+  (
+    'B',
+    &[
+      "#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#",
+      "#####.",
+    ],
+  ),
+  (
+    'C',
+    &[
+      ".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#",
+      ".####.",
+    ],
+  ),
+  (
+    'E',
+    &[
+      "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....",
+      "######",
+    ],
+  ),
+  (
+    'F',
+    &[
+      "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....",
+      "#.....",
+    ],
+  ),
+  (
+    'G',
+    &[
+      ".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##",
+      ".###.#",
+    ],
+  ),
+  (
+    'H',
+    &[
+      "#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#",
+      "#....#",
+    ],
+  ),
+  (
+    'J',
+    &[
+      "...###", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", "#...#.",
+      ".###..",
+    ],
+  ),
+  (
+    'K',
+    &[
+      "#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.",
+      "#....#",
+    ],
+  ),
+  (
+    'L',
+    &[
+      "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+      "######",
+    ],
+  ),
+  (
+    'N',
+    &[
+      "#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##",
+      "#....#",
+    ],
+  ),
+  (
+    'P',
+    &[
+      "#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....",
+      "#.....",
+    ],
+  ),
+  (
+    'R',
+    &[
+      "#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#",
+      "#....#",
+    ],
+  ),
+  (
+    'X',
+    &[
+      "#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#",
+      "#....#",
+    ],
+  ),
+  (
+    'Z',
+    &[
+      "######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....",
+      "######",
+    ],
+  ),
+];
+
+/// Read the letters drawn by the lit pixels.
+pub fn read<I>(lit: I) -> Result<String, String>
+where
+  I: IntoIterator<Item = Point>,
+{
+  let lit: HashSet<Point> = lit.into_iter().collect();
+  let bbox =
+    BoundingBox::from_points(lit.iter().cloned()).ok_or_else(|| "nothing to read".to_owned())?;
+  let font = match bbox.height() {
+    6 => SMALL_FONT,
+    10 => LARGE_FONT,
+    h => return Err(format!("no font is {} pixels high", h)),
+  };
+
+  let column = |x| -> String {
+    (bbox.min.y..=bbox.max.y)
+      .map(|y| {
+        if lit.contains(&Point::new(x, y)) {
+          '#'
+        } else {
+          '.'
+        }
+      })
+      .collect()
+  };
+
+  // split the picture on blank columns; each run of non-blank columns is a letter
+  let mut letters = Vec::new();
+  let mut letter: Vec<String> = Vec::new();
+
+  for x in bbox.min.x..=bbox.max.x + 1 {
+    let col = column(x);
+
+    if col.contains('#') {
+      letter.push(col);
+    } else if !letter.is_empty() {
+      letters.push(letter);
+      letter = Vec::new();
+    }
+  }
+
+  letters
+    .iter()
+    .enumerate()
+    .map(|(i, columns)| {
+      font
+        .iter()
+        .find(|(_, rows)| matches(rows, columns))
+        .map(|&(c, _)| c)
+        .ok_or_else(|| {
+          format!(
+            "unknown letter #{}:\n{}",
+            i + 1,
+            transpose(columns).join("\n")
+          )
+        })
+    })
+    .collect()
+}
+
+/// Whether a glyph, trimmed of its blank columns, is made of the given columns.
+fn matches(rows: &[&str], columns: &[String]) -> bool {
+  let width = rows[0].len();
+  let glyph_columns: Vec<String> = (0..width)
+    .map(|x| rows.iter().map(|row| &row[x..=x]).collect())
+    .filter(|col: &String| col.contains('#'))
+    .collect();
+
+  glyph_columns == columns
+}
+
+fn transpose(columns: &[String]) -> Vec<String> {
+  let height = columns.first().map_or(0, String::len);
+
+  (0..height)
+    .map(|y| columns.iter().map(|col| &col[y..=y]).collect())
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Lit pixels of some text, `#` being lit.
+  fn pixels(picture: &[&str], offset: Point) -> Vec<Point> {
+    picture
+      .iter()
+      .enumerate()
+      .flat_map(|(y, row)| {
+        row
+          .chars()
+          .enumerate()
+          .filter(|&(_, c)| c == '#')
+          .map(move |(x, _)| offset + Point::new(x as i64, y as i64))
+      })
+      .collect()
+  }
+
+  #[test]
+  fn small_font() {
+    let picture = [
+      ".##..###..#....###....##.####..##..#..#.",
+      "#..#.#..#.#....#..#....#....#.#..#.#..#.",
+      "#....###..#....#..#....#...#..#....#..#.",
+      "#....#..#.#....###.....#..#...#....#..#.",
+      "#..#.#..#.#....#....#..#.#....#..#.#..#.",
+      ".##..###..####.#.....##..####..##...##..",
+    ];
+
+    assert_eq!(
+      read(pixels(&picture, Point::new(-3, 7))),
+      Ok("CBLPJZCU".to_owned())
+    );
+
+    let picture = [
+      "#....####...##.#..#..##..",
+      "#....#.......#.#.#..#..#.",
+      "#....###.....#.##...#....",
+      "#....#.......#.#.#..#....",
+      "#....#....#..#.#.#..#..#.",
+      "####.####..##..#..#..##..",
+    ];
+
+    assert_eq!(
+      read(pixels(&picture, Point::ORIGIN)),
+      Ok("LEJKC".to_owned())
+    );
+  }
+
+  #[test]
+  fn large_font() {
+    let glyph = |letter| LARGE_FONT.iter().find(|(c, _)| *c == letter).unwrap().1;
+    let picture: Vec<_> = glyph('H')
+      .iter()
+      .zip(glyph('X'))
+      .map(|(h, x)| format!("{}..{}", h, x))
+      .collect();
+    let picture: Vec<_> = picture.iter().map(String::as_str).collect();
+
+    assert_eq!(read(pixels(&picture, Point::ORIGIN)), Ok("HX".to_owned()));
+  }
+
+  #[test]
+  fn errors() {
+    assert!(read(Vec::new()).is_err());
+    assert!(read(pixels(&["#", "#"], Point::ORIGIN)).is_err());
+    assert!(read(pixels(&["#", "#", "#", "#", "#", "#"], Point::ORIGIN)).is_err());
+  }
+}
@@ -0,0 +1,214 @@
+//! Rendering of painted grids to terminal text or PPM images.
+
+use crate::{BoundingBox, Point};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Colour of a pixel.
+pub type Rgb = [u8; 3];
+
+/// How a cell looks like, on a terminal and in an image.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Swatch {
+  pub glyph: char,
+  pub rgb: Rgb,
+}
+
+impl Swatch {
+  pub const fn new(glyph: char, rgb: Rgb) -> Self {
+    Swatch { glyph, rgb }
+  }
+}
+
+/// Associate cell values to swatches; cells that are absent or unknown use the background.
+#[derive(Clone, Debug)]
+pub struct Palette<T> {
+  background: Swatch,
+  swatches: Vec<(T, Swatch)>,
+}
+
+impl<T> Palette<T>
+where
+  T: PartialEq,
+{
+  pub fn new(background: Swatch) -> Self {
+    Palette {
+      background,
+      swatches: Vec::new(),
+    }
+  }
+
+  /// Use `swatch` for cells equal to `value`.
+  pub fn with(mut self, value: T, swatch: Swatch) -> Self {
+    self.swatches.push((value, swatch));
+    self
+  }
+
+  pub fn swatch(&self, cell: Option<&T>) -> Swatch {
+    cell
+      .and_then(|cell| self.swatches.iter().find(|(value, _)| value == cell))
+      .map_or(self.background, |&(_, swatch)| swatch)
+  }
+}
+
+/// Which way the `y` axis points when rendered.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Orientation {
+  /// Rows are drawn from the smallest `y` to the biggest one (screen coordinates).
+  YDown,
+  /// Rows are drawn from the biggest `y` to the smallest one (mathematical coordinates).
+  YUp,
+}
+
+/// Cells to render, framed by their bounding box.
+#[derive(Clone, Debug)]
+pub struct Canvas<'a, T> {
+  cells: HashMap<Point, &'a T>,
+  bbox: Option<BoundingBox>,
+  orientation: Orientation,
+}
+
+impl<'a, T> Canvas<'a, T> {
+  /// Canvas of any set of cells, such as `SparseGrid::iter` or a `HashMap<[i64; 2], T>`.
+  pub fn new<I, P>(cells: I) -> Self
+  where
+    I: IntoIterator<Item = (P, &'a T)>,
+    P: Into<Point>,
+  {
+    let cells: HashMap<_, _> = cells.into_iter().map(|(p, c)| (p.into(), c)).collect();
+    let bbox = BoundingBox::from_points(cells.keys().cloned());
+
+    Canvas {
+      cells,
+      bbox,
+      orientation: Orientation::YDown,
+    }
+  }
+
+  pub fn orientation(mut self, orientation: Orientation) -> Self {
+    self.orientation = orientation;
+    self
+  }
+
+  /// Rows of cells mapped by `f`, in drawing order.
+  fn rows<'p, S, F>(&'p self, mut f: F) -> impl Iterator<Item = Vec<S>> + 'p
+  where
+    F: FnMut(Option<&T>) -> S + 'p,
+  {
+    let (min, max) = self
+      .bbox
+      .map_or((Point::new(0, 0), Point::new(-1, -1)), |bbox| {
+        (bbox.min, bbox.max)
+      });
+    let ys: Box<dyn Iterator<Item = i64>> = match self.orientation {
+      Orientation::YDown => Box::new(min.y..=max.y),
+      Orientation::YUp => Box::new((min.y..=max.y).rev()),
+    };
+
+    ys.map(move |y| {
+      (min.x..=max.x)
+        .map(|x| f(self.cells.get(&Point::new(x, y)).cloned()))
+        .collect()
+    })
+  }
+
+  /// Render as text, one line per row, `f` giving the glyph of each cell.
+  pub fn glyphs<F>(&self, f: F) -> String
+  where
+    F: FnMut(Option<&T>) -> char,
+  {
+    let mut output = String::new();
+
+    for row in self.rows(f) {
+      output.extend(row);
+      output.push('\n');
+    }
+
+    output
+  }
+}
+
+impl<'a, T> Canvas<'a, T>
+where
+  T: PartialEq,
+{
+  /// Render as text, one line per row.
+  pub fn text(&self, palette: &Palette<T>) -> String {
+    self.glyphs(|cell| palette.swatch(cell).glyph)
+  }
+
+  /// Render as a binary PPM (P6) image, each cell being a `scale`×`scale` square.
+  pub fn ppm(&self, palette: &Palette<T>, scale: usize) -> Vec<u8> {
+    let scale = scale.max(1);
+    let (width, height) = self.bbox.map_or((0, 0), |bbox| {
+      (bbox.width() as usize, bbox.height() as usize)
+    });
+    let mut image = format!("P6\n{} {}\n255\n", width * scale, height * scale).into_bytes();
+
+    for row in self.rows(|cell| palette.swatch(cell)) {
+      let line: Vec<u8> = row
+        .iter()
+        .flat_map(|swatch| swatch.rgb.repeat(scale))
+        .collect();
+
+      for _ in 0..scale {
+        image.extend_from_slice(&line);
+      }
+    }
+
+    image
+  }
+
+  pub fn save_ppm<P>(&self, palette: &Palette<T>, scale: usize, path: P) -> Result<(), String>
+  where
+    P: AsRef<Path>,
+  {
+    let path = path.as_ref();
+    fs::write(path, self.ppm(palette, scale))
+      .map_err(|e| format!("cannot write image {}: {}", path.display(), e))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const WHITE: Rgb = [255, 255, 255];
+  const BLACK: Rgb = [0, 0, 0];
+
+  fn palette() -> Palette<i64> {
+    Palette::new(Swatch::new('.', BLACK)).with(1, Swatch::new('#', WHITE))
+  }
+
+  #[test]
+  fn text() {
+    let mut cells = HashMap::new();
+    cells.insert([1, 5], 1);
+    cells.insert([2, 4], 0);
+    cells.insert([3, 4], 1);
+
+    let canvas = Canvas::new(cells.iter().map(|(&p, c)| (p, c)));
+    assert_eq!(canvas.text(&palette()), "..#\n#..\n");
+
+    let canvas = canvas.orientation(Orientation::YUp);
+    assert_eq!(canvas.text(&palette()), "#..\n..#\n");
+
+    assert_eq!(
+      Canvas::new(Vec::<(Point, &i64)>::new()).text(&palette()),
+      ""
+    );
+  }
+
+  #[test]
+  fn ppm() {
+    let cells = vec![(Point::new(0, 0), &1), (Point::new(1, 0), &0)];
+    let ppm = Canvas::new(cells).ppm(&palette(), 2);
+    let header = b"P6\n4 2\n255\n";
+
+    assert_eq!(&ppm[..header.len()], header);
+
+    let row = [WHITE, WHITE, BLACK, BLACK].concat();
+    assert_eq!(&ppm[header.len()..], [row.clone(), row].concat().as_slice());
+  }
+}
@@ -0,0 +1,118 @@
+use crate::render::Canvas;
+use crate::{BoundingBox, Point};
+use std::collections::hash_map::{self, HashMap};
+use std::iter::FromIterator;
+
+/// Unbounded grid storing only the cells that were set.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SparseGrid<T> {
+  cells: HashMap<Point, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+  fn default() -> Self {
+    SparseGrid {
+      cells: HashMap::new(),
+    }
+  }
+}
+
+impl<T> SparseGrid<T> {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Number of cells that were set.
+  pub fn len(&self) -> usize {
+    self.cells.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.cells.is_empty()
+  }
+
+  pub fn get(&self, p: Point) -> Option<&T> {
+    self.cells.get(&p)
+  }
+
+  pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+    self.cells.get_mut(&p)
+  }
+
+  /// Set a cell, returning its previous value.
+  pub fn insert(&mut self, p: Point, value: T) -> Option<T> {
+    self.cells.insert(p, value)
+  }
+
+  pub fn remove(&mut self, p: Point) -> Option<T> {
+    self.cells.remove(&p)
+  }
+
+  pub fn entry(&mut self, p: Point) -> hash_map::Entry<'_, Point, T> {
+    self.cells.entry(p)
+  }
+
+  /// Cells that were set, in no particular order.
+  pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+    self.cells.iter().map(|(&p, cell)| (p, cell))
+  }
+
+  /// Smallest box containing every cell that was set.
+  pub fn bounding_box(&self) -> Option<BoundingBox> {
+    BoundingBox::from_points(self.cells.keys().cloned())
+  }
+
+  /// Render the bounding box of the grid as text, one line per row.
+  pub fn render<F>(&self, f: F) -> String
+  where
+    F: FnMut(Option<&T>) -> char,
+  {
+    Canvas::new(self.iter()).glyphs(f)
+  }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+  fn from_iter<I>(iter: I) -> Self
+  where
+    I: IntoIterator<Item = (Point, T)>,
+  {
+    SparseGrid {
+      cells: iter.into_iter().collect(),
+    }
+  }
+}
+
+impl<T> IntoIterator for SparseGrid<T> {
+  type Item = (Point, T);
+  type IntoIter = hash_map::IntoIter<Point, T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.cells.into_iter()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn sparse() {
+    let mut grid: SparseGrid<_> = vec![(Point::new(-1, 0), 'a'), (Point::new(1, 1), 'b')]
+      .into_iter()
+      .collect();
+
+    assert_eq!(grid.len(), 2);
+    assert_eq!(grid.insert(Point::new(1, 1), 'c'), Some('b'));
+    assert_eq!(grid.get(Point::ORIGIN), None);
+    assert_eq!(
+      grid.bounding_box(),
+      Some(BoundingBox {
+        min: Point::new(-1, 0),
+        max: Point::new(1, 1)
+      })
+    );
+    assert_eq!(SparseGrid::<char>::new().bounding_box(), None);
+    assert_eq!(grid.render(|c| c.cloned().unwrap_or('.')), "a..\n..c\n");
+    assert_eq!(SparseGrid::<char>::new().render(|_| '.'), "");
+  }
+}
@@ -0,0 +1,1266 @@
+//! A tiny structured language compiled down to intcode.
+//!
+//! A program is a list of functions, one of which must be `main`:
+//!
+//! ```text
+//! fn fact(n) {
+//!   if n < 2 {
+//!     return 1;
+//!   }
+//!
+//!   return n * fact(n - 1);
+//! }
+//!
+//! fn main() {
+//!   let n = input();
+//!   output(fact(n));
+//! }
+//! ```
+//!
+//! Supported constructs are `let` bindings, assignments, `if` / `else`, `while`, `return`, function
+//! calls and the `input()` / `output(x)` builtins. Expressions are made of integer literals,
+//! variables, calls and the `+ - * < > <= >= == != && || !` operators; as in C, `&&` and `||` only
+//! evaluate their right operand when the left one doesn’t decide the result.
+//!
+//! Functions use the relative base as a frame pointer: every call moves the relative base forward
+//! by the size of the caller’s frame with `AdjustRelBase`, and moves it back on return. The stack
+//! lives right after the code.
+
+use std::collections::HashMap;
+
+use crate::Word;
+
+/// Compile a source program into intcode words.
+pub fn compile(source: &str) -> Result<Vec<Word>, String> {
+  let tokens = tokenize(source)?;
+  let functions = Parser::new(tokens).parse_program()?;
+  Codegen::new(&functions)?.generate(&functions)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Token {
+  Ident(String),
+  Number(Word),
+  Fn,
+  Let,
+  If,
+  Else,
+  While,
+  Return,
+  LParen,
+  RParen,
+  LBrace,
+  RBrace,
+  Comma,
+  Semicolon,
+  Assign,
+  Plus,
+  Minus,
+  Star,
+  Lt,
+  Gt,
+  Le,
+  Ge,
+  Eq,
+  Ne,
+  Not,
+  And,
+  Or,
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, String> {
+  let mut tokens = Vec::new();
+
+  for (i, line) in source.lines().enumerate() {
+    let line_nb = i + 1;
+    let bytes = line.as_bytes();
+    let mut j = 0;
+
+    while j < bytes.len() {
+      let c = bytes[j];
+
+      if c.is_ascii_whitespace() {
+        j += 1;
+        continue;
+      }
+
+      // comments run until the end of the line
+      if line[j..].starts_with("//") {
+        break;
+      }
+
+      if c.is_ascii_digit() {
+        let start = j;
+        while j < bytes.len() && bytes[j].is_ascii_digit() {
+          j += 1;
+        }
+
+        let n = line[start..j]
+          .parse()
+          .map_err(|e| format!("line {}: cannot parse number: {}", line_nb, e))?;
+        tokens.push((Token::Number(n), line_nb));
+        continue;
+      }
+
+      if c.is_ascii_alphabetic() || c == b'_' {
+        let start = j;
+        while j < bytes.len() && (bytes[j].is_ascii_alphanumeric() || bytes[j] == b'_') {
+          j += 1;
+        }
+
+        let token = match &line[start..j] {
+          "fn" => Token::Fn,
+          "let" => Token::Let,
+          "if" => Token::If,
+          "else" => Token::Else,
+          "while" => Token::While,
+          "return" => Token::Return,
+          ident => Token::Ident(ident.to_owned()),
+        };
+        tokens.push((token, line_nb));
+        continue;
+      }
+
+      // compare bytes, as the next byte may be inside a multi-byte character
+      let two = bytes.get(j..j + 2);
+
+      let (token, len) = match two {
+        Some(b"<=") => (Token::Le, 2),
+        Some(b">=") => (Token::Ge, 2),
+        Some(b"==") => (Token::Eq, 2),
+        Some(b"!=") => (Token::Ne, 2),
+        Some(b"&&") => (Token::And, 2),
+        Some(b"||") => (Token::Or, 2),
+        _ => {
+          let token = match c {
+            b'(' => Token::LParen,
+            b')' => Token::RParen,
+            b'{' => Token::LBrace,
+            b'}' => Token::RBrace,
+            b',' => Token::Comma,
+            b';' => Token::Semicolon,
+            b'=' => Token::Assign,
+            b'+' => Token::Plus,
+            b'-' => Token::Minus,
+            b'*' => Token::Star,
+            b'<' => Token::Lt,
+            b'>' => Token::Gt,
+            b'!' => Token::Not,
+            _ => {
+              let c = line[j..].chars().next().unwrap();
+              let column = line[..j].chars().count() + 1;
+
+              return Err(format!(
+                "line {}, column {}: unexpected character: {:?}",
+                line_nb, column, c
+              ));
+            }
+          };
+
+          (token, 1)
+        }
+      };
+
+      tokens.push((token, line_nb));
+      j += len;
+    }
+  }
+
+  Ok(tokens)
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum BinOp {
+  Add,
+  Sub,
+  Mul,
+  Lt,
+  Gt,
+  Le,
+  Ge,
+  Eq,
+  Ne,
+  And,
+  Or,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum UnOp {
+  Neg,
+  Not,
+}
+
+#[derive(Clone, Debug)]
+enum Expr {
+  Number(Word),
+  Var(String, usize),
+  Call(String, Vec<Expr>, usize),
+  Unary(UnOp, Box<Expr>),
+  Binary(BinOp, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Clone, Debug)]
+enum Stmt {
+  Let(String, Expr),
+  Assign(String, Expr, usize),
+  If(Expr, Vec<Stmt>, Vec<Stmt>),
+  While(Expr, Vec<Stmt>),
+  Return(Option<Expr>),
+  Expr(Expr),
+}
+
+#[derive(Clone, Debug)]
+struct Function {
+  name: String,
+  params: Vec<String>,
+  body: Vec<Stmt>,
+  line: usize,
+}
+
+struct Parser {
+  tokens: Vec<(Token, usize)>,
+  pos: usize,
+}
+
+impl Parser {
+  fn new(tokens: Vec<(Token, usize)>) -> Self {
+    Parser { tokens, pos: 0 }
+  }
+
+  fn peek(&self) -> Option<&Token> {
+    self.tokens.get(self.pos).map(|(t, _)| t)
+  }
+
+  fn line(&self) -> usize {
+    self
+      .tokens
+      .get(self.pos)
+      .or_else(|| self.tokens.last())
+      .map_or(1, |(_, line)| *line)
+  }
+
+  fn next(&mut self) -> Result<Token, String> {
+    let token = self
+      .tokens
+      .get(self.pos)
+      .map(|(t, _)| t.clone())
+      .ok_or_else(|| format!("line {}: unexpected end of input", self.line()))?;
+    self.pos += 1;
+    Ok(token)
+  }
+
+  fn eat(&mut self, token: &Token) -> bool {
+    if self.peek() == Some(token) {
+      self.pos += 1;
+      true
+    } else {
+      false
+    }
+  }
+
+  fn expect(&mut self, token: Token) -> Result<(), String> {
+    let line = self.line();
+    let found = self.next()?;
+
+    if found == token {
+      Ok(())
+    } else {
+      Err(format!(
+        "line {}: expected {:?}, found {:?}",
+        line, token, found
+      ))
+    }
+  }
+
+  fn expect_ident(&mut self) -> Result<String, String> {
+    let line = self.line();
+
+    match self.next()? {
+      Token::Ident(ident) => Ok(ident),
+      found => Err(format!(
+        "line {}: expected identifier, found {:?}",
+        line, found
+      )),
+    }
+  }
+
+  fn parse_program(&mut self) -> Result<Vec<Function>, String> {
+    let mut functions = Vec::new();
+
+    while self.peek().is_some() {
+      functions.push(self.parse_function()?);
+    }
+
+    Ok(functions)
+  }
+
+  fn parse_function(&mut self) -> Result<Function, String> {
+    let line = self.line();
+    self.expect(Token::Fn)?;
+    let name = self.expect_ident()?;
+    self.expect(Token::LParen)?;
+
+    let mut params = Vec::new();
+    if !self.eat(&Token::RParen) {
+      loop {
+        params.push(self.expect_ident()?);
+
+        if self.eat(&Token::RParen) {
+          break;
+        }
+
+        self.expect(Token::Comma)?;
+      }
+    }
+
+    let body = self.parse_block()?;
+
+    Ok(Function {
+      name,
+      params,
+      body,
+      line,
+    })
+  }
+
+  fn parse_block(&mut self) -> Result<Vec<Stmt>, String> {
+    self.expect(Token::LBrace)?;
+
+    let mut stmts = Vec::new();
+    while !self.eat(&Token::RBrace) {
+      stmts.push(self.parse_stmt()?);
+    }
+
+    Ok(stmts)
+  }
+
+  fn parse_stmt(&mut self) -> Result<Stmt, String> {
+    match self.peek() {
+      Some(Token::Let) => {
+        self.pos += 1;
+        let name = self.expect_ident()?;
+        self.expect(Token::Assign)?;
+        let expr = self.parse_expr()?;
+        self.expect(Token::Semicolon)?;
+        Ok(Stmt::Let(name, expr))
+      }
+
+      Some(Token::If) => self.parse_if(),
+
+      Some(Token::While) => {
+        self.pos += 1;
+        let cond = self.parse_expr()?;
+        let body = self.parse_block()?;
+        Ok(Stmt::While(cond, body))
+      }
+
+      Some(Token::Return) => {
+        self.pos += 1;
+
+        if self.eat(&Token::Semicolon) {
+          Ok(Stmt::Return(None))
+        } else {
+          let expr = self.parse_expr()?;
+          self.expect(Token::Semicolon)?;
+          Ok(Stmt::Return(Some(expr)))
+        }
+      }
+
+      Some(Token::Ident(_))
+        if self.tokens.get(self.pos + 1).map(|(t, _)| t) == Some(&Token::Assign) =>
+      {
+        let line = self.line();
+        let name = self.expect_ident()?;
+        self.expect(Token::Assign)?;
+        let expr = self.parse_expr()?;
+        self.expect(Token::Semicolon)?;
+        Ok(Stmt::Assign(name, expr, line))
+      }
+
+      _ => {
+        let expr = self.parse_expr()?;
+        self.expect(Token::Semicolon)?;
+        Ok(Stmt::Expr(expr))
+      }
+    }
+  }
+
+  fn parse_if(&mut self) -> Result<Stmt, String> {
+    self.expect(Token::If)?;
+    let cond = self.parse_expr()?;
+    let then_branch = self.parse_block()?;
+
+    let else_branch = if self.eat(&Token::Else) {
+      if self.peek() == Some(&Token::If) {
+        vec![self.parse_if()?]
+      } else {
+        self.parse_block()?
+      }
+    } else {
+      Vec::new()
+    };
+
+    Ok(Stmt::If(cond, then_branch, else_branch))
+  }
+
+  fn parse_expr(&mut self) -> Result<Expr, String> {
+    self.parse_binary(0)
+  }
+
+  /// Precedence climbing; `level` indexes the table of binary operators, from the loosest to the
+  /// tightest.
+  fn parse_binary(&mut self, level: usize) -> Result<Expr, String> {
+    const LEVELS: &[&[(Token, BinOp)]] = &[
+      &[(Token::Or, BinOp::Or)],
+      &[(Token::And, BinOp::And)],
+      &[
+        (Token::Eq, BinOp::Eq),
+        (Token::Ne, BinOp::Ne),
+        (Token::Lt, BinOp::Lt),
+        (Token::Gt, BinOp::Gt),
+        (Token::Le, BinOp::Le),
+        (Token::Ge, BinOp::Ge),
+      ],
+      &[(Token::Plus, BinOp::Add), (Token::Minus, BinOp::Sub)],
+      &[(Token::Star, BinOp::Mul)],
+    ];
+
+    if level == LEVELS.len() {
+      return self.parse_unary();
+    }
+
+    let mut lhs = self.parse_binary(level + 1)?;
+
+    'outer: loop {
+      for (token, op) in LEVELS[level] {
+        if self.eat(token) {
+          let rhs = self.parse_binary(level + 1)?;
+          lhs = Expr::Binary(*op, Box::new(lhs), Box::new(rhs));
+          continue 'outer;
+        }
+      }
+
+      break Ok(lhs);
+    }
+  }
+
+  fn parse_unary(&mut self) -> Result<Expr, String> {
+    if self.eat(&Token::Minus) {
+      Ok(Expr::Unary(UnOp::Neg, Box::new(self.parse_unary()?)))
+    } else if self.eat(&Token::Not) {
+      Ok(Expr::Unary(UnOp::Not, Box::new(self.parse_unary()?)))
+    } else {
+      self.parse_primary()
+    }
+  }
+
+  fn parse_primary(&mut self) -> Result<Expr, String> {
+    let line = self.line();
+
+    match self.next()? {
+      Token::Number(n) => Ok(Expr::Number(n)),
+
+      Token::Ident(name) => {
+        if self.eat(&Token::LParen) {
+          let mut args = Vec::new();
+
+          if !self.eat(&Token::RParen) {
+            loop {
+              args.push(self.parse_expr()?);
+
+              if self.eat(&Token::RParen) {
+                break;
+              }
+
+              self.expect(Token::Comma)?;
+            }
+          }
+
+          Ok(Expr::Call(name, args, line))
+        } else {
+          Ok(Expr::Var(name, line))
+        }
+      }
+
+      Token::LParen => {
+        let expr = self.parse_expr()?;
+        self.expect(Token::RParen)?;
+        Ok(expr)
+      }
+
+      found => Err(format!("line {}: unexpected token: {:?}", line, found)),
+    }
+  }
+}
+
+/// An instruction argument, before addresses and frame sizes are known.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Arg {
+  /// Immediate value.
+  Imm(Word),
+  /// Immediate address of a label.
+  Label(usize),
+  /// Immediate size of the current frame, multiplied by the given sign.
+  FrameSize(Word),
+  /// Slot in the current frame.
+  Local(usize),
+  /// Slot in the frame of the function being called, right after the current frame.
+  Callee(usize),
+}
+
+impl Arg {
+  fn mode(self) -> Word {
+    match self {
+      Arg::Imm(_) | Arg::Label(_) | Arg::FrameSize(_) => 1,
+      Arg::Local(_) | Arg::Callee(_) => 2,
+    }
+  }
+}
+
+// opcodes
+const ADD: Word = 1;
+const MUL: Word = 2;
+const IN: Word = 3;
+const OUT: Word = 4;
+const JUMP_IF_TRUE: Word = 5;
+const JUMP_IF_FALSE: Word = 6;
+const LESS_THAN: Word = 7;
+const EQUALS: Word = 8;
+const ADJUST_REL_BASE: Word = 9;
+const HALT: Word = 99;
+
+// frame layout: the return address lives in slot 0, arguments start at slot 1 and the return value
+// is written back to slot 1
+const RET_ADDR_SLOT: usize = 0;
+const RET_VALUE_SLOT: usize = 1;
+const MIN_FRAME_SIZE: usize = 2;
+
+struct Codegen<'a> {
+  code: Vec<Word>,
+  labels: Vec<Option<usize>>,
+  label_fixups: Vec<(usize, usize)>,
+  frame_fixups: Vec<(usize, Arg)>,
+  functions: HashMap<&'a str, (usize, usize)>,
+  scopes: Vec<HashMap<&'a str, usize>>,
+  next_slot: usize,
+  frame_size: usize,
+}
+
+impl<'a> Codegen<'a> {
+  fn new(functions: &'a [Function]) -> Result<Self, String> {
+    let mut codegen = Codegen {
+      code: Vec::new(),
+      labels: Vec::new(),
+      label_fixups: Vec::new(),
+      frame_fixups: Vec::new(),
+      functions: HashMap::new(),
+      scopes: Vec::new(),
+      next_slot: 0,
+      frame_size: 0,
+    };
+
+    for f in functions {
+      if f.name == "input" || f.name == "output" {
+        return Err(format!(
+          "line {}: cannot redefine builtin {}",
+          f.line, f.name
+        ));
+      }
+
+      let label = codegen.new_label();
+      if codegen
+        .functions
+        .insert(&f.name, (label, f.params.len()))
+        .is_some()
+      {
+        return Err(format!(
+          "line {}: function {} defined twice",
+          f.line, f.name
+        ));
+      }
+    }
+
+    Ok(codegen)
+  }
+
+  fn generate(mut self, functions: &'a [Function]) -> Result<Vec<Word>, String> {
+    let &(main, main_arity) = self
+      .functions
+      .get("main")
+      .ok_or_else(|| "no main function".to_owned())?;
+
+    if main_arity != 0 {
+      return Err("main cannot take arguments".to_owned());
+    }
+
+    // prologue: point the relative base at the stack, call main and halt when it returns
+    let stack = self.new_label();
+    let halt = self.new_label();
+    self.emit(ADJUST_REL_BASE, &[Arg::Label(stack)]);
+    self.emit(
+      ADD,
+      &[Arg::Label(halt), Arg::Imm(0), Arg::Local(RET_ADDR_SLOT)],
+    );
+    self.emit(JUMP_IF_TRUE, &[Arg::Imm(1), Arg::Label(main)]);
+    self.place(halt);
+    self.code.push(HALT);
+
+    for f in functions {
+      self.gen_function(f)?;
+    }
+
+    self.place(stack);
+
+    for (at, label) in self.label_fixups.drain(..) {
+      self.code[at] = self.labels[label].expect("unplaced label") as Word;
+    }
+
+    Ok(self.code)
+  }
+
+  fn new_label(&mut self) -> usize {
+    self.labels.push(None);
+    self.labels.len() - 1
+  }
+
+  fn place(&mut self, label: usize) {
+    self.labels[label] = Some(self.code.len());
+  }
+
+  fn emit(&mut self, opcode: Word, args: &[Arg]) {
+    let modes = args
+      .iter()
+      .rev()
+      .fold(0, |modes, arg| modes * 10 + arg.mode());
+    self.code.push(opcode + modes * 100);
+
+    for &arg in args {
+      let at = self.code.len();
+
+      let value = match arg {
+        Arg::Imm(value) => value,
+        Arg::Local(slot) => slot as Word,
+        Arg::Label(label) => {
+          self.label_fixups.push((at, label));
+          0
+        }
+        Arg::FrameSize(_) | Arg::Callee(_) => {
+          self.frame_fixups.push((at, arg));
+          0
+        }
+      };
+
+      self.code.push(value);
+    }
+  }
+
+  fn alloc_slot(&mut self) -> usize {
+    let slot = self.next_slot;
+    self.next_slot += 1;
+    self.frame_size = self.frame_size.max(self.next_slot);
+    slot
+  }
+
+  fn lookup(&self, name: &str, line: usize) -> Result<usize, String> {
+    self
+      .scopes
+      .iter()
+      .rev()
+      .find_map(|scope| scope.get(name).copied())
+      .ok_or_else(|| format!("line {}: unknown variable {}", line, name))
+  }
+
+  fn gen_function(&mut self, f: &'a Function) -> Result<(), String> {
+    let (label, _) = self.functions[f.name.as_str()];
+    self.place(label);
+
+    let mut params = HashMap::new();
+    for (i, param) in f.params.iter().enumerate() {
+      if params.insert(param.as_str(), i + 1).is_some() {
+        return Err(format!("line {}: duplicate parameter {}", f.line, param));
+      }
+    }
+
+    self.scopes = vec![params];
+    self.next_slot = 1 + f.params.len();
+    self.frame_size = self.next_slot.max(MIN_FRAME_SIZE);
+
+    self.gen_block(&f.body)?;
+
+    // implicit return 0
+    self.gen_return(Arg::Imm(0));
+
+    let frame_size = self.frame_size as Word;
+    for (at, arg) in self.frame_fixups.drain(..) {
+      self.code[at] = match arg {
+        Arg::FrameSize(sign) => sign * frame_size,
+        Arg::Callee(slot) => frame_size + slot as Word,
+        _ => unreachable!(),
+      };
+    }
+
+    Ok(())
+  }
+
+  fn gen_block(&mut self, stmts: &'a [Stmt]) -> Result<(), String> {
+    let next_slot = self.next_slot;
+    self.scopes.push(HashMap::new());
+
+    for stmt in stmts {
+      self.gen_stmt(stmt)?;
+    }
+
+    self.scopes.pop();
+    self.next_slot = next_slot;
+
+    Ok(())
+  }
+
+  fn gen_stmt(&mut self, stmt: &'a Stmt) -> Result<(), String> {
+    // temporaries only live for the duration of a statement
+    let next_slot = self.next_slot;
+
+    match stmt {
+      Stmt::Let(name, expr) => {
+        let value = self.gen_expr(expr)?;
+        self.next_slot = next_slot;
+        let slot = self.alloc_slot();
+        self.emit(ADD, &[value, Arg::Imm(0), Arg::Local(slot)]);
+        self.scopes.last_mut().unwrap().insert(name, slot);
+        return Ok(());
+      }
+
+      Stmt::Assign(name, expr, line) => {
+        let slot = self.lookup(name, *line)?;
+        let value = self.gen_expr(expr)?;
+        self.emit(ADD, &[value, Arg::Imm(0), Arg::Local(slot)]);
+      }
+
+      Stmt::If(cond, then_branch, else_branch) => {
+        let else_label = self.new_label();
+        let end_label = self.new_label();
+
+        let cond = self.gen_expr(cond)?;
+        self.next_slot = next_slot;
+        self.emit(JUMP_IF_FALSE, &[cond, Arg::Label(else_label)]);
+        self.gen_block(then_branch)?;
+        self.emit(JUMP_IF_TRUE, &[Arg::Imm(1), Arg::Label(end_label)]);
+        self.place(else_label);
+        self.gen_block(else_branch)?;
+        self.place(end_label);
+      }
+
+      Stmt::While(cond, body) => {
+        let top_label = self.new_label();
+        let end_label = self.new_label();
+
+        self.place(top_label);
+        let cond = self.gen_expr(cond)?;
+        self.next_slot = next_slot;
+        self.emit(JUMP_IF_FALSE, &[cond, Arg::Label(end_label)]);
+        self.gen_block(body)?;
+        self.emit(JUMP_IF_TRUE, &[Arg::Imm(1), Arg::Label(top_label)]);
+        self.place(end_label);
+      }
+
+      Stmt::Return(expr) => {
+        let value = match expr {
+          Some(expr) => self.gen_expr(expr)?,
+          None => Arg::Imm(0),
+        };
+
+        self.gen_return(value);
+      }
+
+      Stmt::Expr(expr) => {
+        self.gen_expr(expr)?;
+      }
+    }
+
+    self.next_slot = next_slot;
+    Ok(())
+  }
+
+  fn gen_return(&mut self, value: Arg) {
+    self.emit(ADD, &[value, Arg::Imm(0), Arg::Local(RET_VALUE_SLOT)]);
+    self.emit(JUMP_IF_TRUE, &[Arg::Imm(1), Arg::Local(RET_ADDR_SLOT)]);
+  }
+
+  /// Generate the code of an expression and return the argument holding its value.
+  fn gen_expr(&mut self, expr: &'a Expr) -> Result<Arg, String> {
+    match expr {
+      Expr::Number(n) => Ok(Arg::Imm(*n)),
+
+      Expr::Var(name, line) => self.lookup(name, *line).map(Arg::Local),
+
+      Expr::Call(name, args, line) => self.gen_call(name, args, *line),
+
+      Expr::Unary(op, expr) => {
+        let value = self.gen_expr(expr)?;
+
+        if let Arg::Imm(n) = value {
+          return match op {
+            UnOp::Neg => n
+              .checked_neg()
+              .map(Arg::Imm)
+              .ok_or_else(|| format!("constant expression overflows: -{}", n)),
+            UnOp::Not => Ok(Arg::Imm((n == 0) as Word)),
+          };
+        }
+
+        let result = Arg::Local(self.alloc_slot());
+        match op {
+          UnOp::Neg => self.emit(MUL, &[value, Arg::Imm(-1), result]),
+          UnOp::Not => self.emit(EQUALS, &[value, Arg::Imm(0), result]),
+        }
+
+        Ok(result)
+      }
+
+      Expr::Binary(op, lhs, rhs) if *op == BinOp::And || *op == BinOp::Or => {
+        self.gen_short_circuit(*op, lhs, rhs)
+      }
+
+      Expr::Binary(op, lhs, rhs) => {
+        let a = self.gen_expr(lhs)?;
+        let b = self.gen_expr(rhs)?;
+
+        if let (Arg::Imm(a), Arg::Imm(b)) = (a, b) {
+          return fold(*op, a, b)
+            .map(Arg::Imm)
+            .ok_or_else(|| format!("constant expression overflows: {} {:?} {}", a, op, b));
+        }
+
+        let result = Arg::Local(self.alloc_slot());
+
+        match op {
+          BinOp::Add => self.emit(ADD, &[a, b, result]),
+          BinOp::Mul => self.emit(MUL, &[a, b, result]),
+          BinOp::Sub => {
+            self.emit(MUL, &[b, Arg::Imm(-1), result]);
+            self.emit(ADD, &[a, result, result]);
+          }
+          BinOp::Lt => self.emit(LESS_THAN, &[a, b, result]),
+          BinOp::Gt => self.emit(LESS_THAN, &[b, a, result]),
+          BinOp::Le => {
+            self.emit(LESS_THAN, &[b, a, result]);
+            self.emit(EQUALS, &[result, Arg::Imm(0), result]);
+          }
+          BinOp::Ge => {
+            self.emit(LESS_THAN, &[a, b, result]);
+            self.emit(EQUALS, &[result, Arg::Imm(0), result]);
+          }
+          BinOp::Eq => self.emit(EQUALS, &[a, b, result]),
+          BinOp::Ne => {
+            self.emit(EQUALS, &[a, b, result]);
+            self.emit(EQUALS, &[result, Arg::Imm(0), result]);
+          }
+          BinOp::And | BinOp::Or => unreachable!(),
+        }
+
+        Ok(result)
+      }
+    }
+  }
+
+  /// Generate `lhs && rhs` or `lhs || rhs`, the right operand being only evaluated when the left
+  /// one doesn’t decide the result.
+  fn gen_short_circuit(&mut self, op: BinOp, lhs: &'a Expr, rhs: &'a Expr) -> Result<Arg, String> {
+    let a = self.gen_expr(lhs)?;
+
+    if let Arg::Imm(a) = a {
+      // false for `&&`, true for `||`
+      if (op == BinOp::And) == (a == 0) {
+        return Ok(Arg::Imm((a != 0) as Word));
+      }
+
+      let b = self.gen_expr(rhs)?;
+      return Ok(self.gen_bool(b));
+    }
+
+    let result = Arg::Local(self.alloc_slot());
+    let end_label = self.new_label();
+    let jump = if op == BinOp::And {
+      JUMP_IF_FALSE
+    } else {
+      JUMP_IF_TRUE
+    };
+
+    self.emit(EQUALS, &[a, Arg::Imm(0), result]);
+    self.emit(EQUALS, &[result, Arg::Imm(0), result]);
+    self.emit(jump, &[result, Arg::Label(end_label)]);
+
+    let b = self.gen_expr(rhs)?;
+    self.emit(EQUALS, &[b, Arg::Imm(0), result]);
+    self.emit(EQUALS, &[result, Arg::Imm(0), result]);
+    self.place(end_label);
+
+    Ok(result)
+  }
+
+  /// Normalize a value to 0 or 1.
+  fn gen_bool(&mut self, value: Arg) -> Arg {
+    if let Arg::Imm(n) = value {
+      return Arg::Imm((n != 0) as Word);
+    }
+
+    let result = Arg::Local(self.alloc_slot());
+    self.emit(EQUALS, &[value, Arg::Imm(0), result]);
+    self.emit(EQUALS, &[result, Arg::Imm(0), result]);
+    result
+  }
+
+  fn gen_call(&mut self, name: &'a str, args: &'a [Expr], line: usize) -> Result<Arg, String> {
+    match (name, args.len()) {
+      ("input", 0) => {
+        let result = Arg::Local(self.alloc_slot());
+        self.emit(IN, &[result]);
+        return Ok(result);
+      }
+
+      ("output", 1) => {
+        let value = self.gen_expr(&args[0])?;
+        self.emit(OUT, &[value]);
+        return Ok(Arg::Imm(0));
+      }
+
+      ("input", _) | ("output", _) => {
+        return Err(format!(
+          "line {}: wrong number of arguments to {}",
+          line, name
+        ));
+      }
+
+      _ => (),
+    }
+
+    let (label, arity) = *self
+      .functions
+      .get(name)
+      .ok_or_else(|| format!("line {}: unknown function {}", line, name))?;
+
+    if arity != args.len() {
+      return Err(format!(
+        "line {}: {} expects {} arguments, got {}",
+        line,
+        name,
+        arity,
+        args.len()
+      ));
+    }
+
+    // evaluate all arguments first, as nested calls reuse the callee frame
+    let values = args
+      .iter()
+      .map(|arg| self.gen_expr(arg))
+      .collect::<Result<Vec<_>, _>>()?;
+
+    for (i, value) in values.into_iter().enumerate() {
+      self.emit(ADD, &[value, Arg::Imm(0), Arg::Callee(1 + i)]);
+    }
+
+    let ret_label = self.new_label();
+    self.emit(
+      ADD,
+      &[
+        Arg::Label(ret_label),
+        Arg::Imm(0),
+        Arg::Callee(RET_ADDR_SLOT),
+      ],
+    );
+    self.emit(ADJUST_REL_BASE, &[Arg::FrameSize(1)]);
+    self.emit(JUMP_IF_TRUE, &[Arg::Imm(1), Arg::Label(label)]);
+    self.place(ret_label);
+    self.emit(ADJUST_REL_BASE, &[Arg::FrameSize(-1)]);
+
+    let result = Arg::Local(self.alloc_slot());
+    self.emit(ADD, &[Arg::Callee(RET_VALUE_SLOT), Arg::Imm(0), result]);
+
+    Ok(result)
+  }
+}
+
+/// Value of an operation on constants, if it doesn’t overflow.
+fn fold(op: BinOp, a: Word, b: Word) -> Option<Word> {
+  let value = match op {
+    BinOp::Add => return a.checked_add(b),
+    BinOp::Sub => return a.checked_sub(b),
+    BinOp::Mul => return a.checked_mul(b),
+    BinOp::Lt => (a < b) as Word,
+    BinOp::Gt => (a > b) as Word,
+    BinOp::Le => (a <= b) as Word,
+    BinOp::Ge => (a >= b) as Word,
+    BinOp::Eq => (a == b) as Word,
+    BinOp::Ne => (a != b) as Word,
+    BinOp::And => (a != 0 && b != 0) as Word,
+    BinOp::Or => (a != 0 || b != 0) as Word,
+  };
+
+  Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{Program, Suspended};
+
+  /// Compile and run a program, collecting all of its outputs.
+  fn outputs(source: &str, inputs: &[Word]) -> Vec<Word> {
+    let mut program = Program::from_words(&compile(source).unwrap());
+    let mut suspended = program.run_suspended(inputs).unwrap();
+    let mut outputs = Vec::new();
+
+    while let Suspended::Running { output, .. } = suspended {
+      outputs.extend(output);
+      suspended = program.rerun(suspended).unwrap();
+    }
+
+    outputs
+  }
+
+  /// Compile and run a program, returning its last output.
+  fn last_output(source: &str, inputs: &[Word]) -> Option<Word> {
+    let mut program = Program::from_words(&compile(source).unwrap());
+    program.run(inputs).unwrap()
+  }
+
+  #[test]
+  fn echo() {
+    const SOURCE: &str = "fn main() { output(input()); }";
+
+    assert_eq!(last_output(SOURCE, &[42]), Some(42));
+    assert_eq!(last_output(SOURCE, &[-7]), Some(-7));
+  }
+
+  #[test]
+  fn arithmetic() {
+    const SOURCE: &str = r#"
+      fn main() {
+        let a = input();
+        let b = input();
+        output(a + b);
+        output(a - b);
+        output(a * b);
+        output(-a + 2 * (b - 1));
+      }
+    "#;
+
+    assert_eq!(outputs(SOURCE, &[7, 3]), vec![10, 4, 21, -3]);
+  }
+
+  #[test]
+  fn comparisons() {
+    const SOURCE: &str = r#"
+      fn main() {
+        let a = input();
+        let b = input();
+        output(a < b);
+        output(a > b);
+        output(a <= b);
+        output(a >= b);
+        output(a == b);
+        output(a != b);
+        output(a < b && b > 0);
+        output(a > b || b == 0);
+        output(!a);
+      }
+    "#;
+
+    assert_eq!(outputs(SOURCE, &[1, 2]), vec![1, 0, 1, 0, 0, 1, 1, 0, 0]);
+    assert_eq!(outputs(SOURCE, &[2, 2]), vec![0, 0, 1, 1, 1, 0, 0, 0, 0]);
+    assert_eq!(outputs(SOURCE, &[0, 0]), vec![0, 0, 1, 1, 1, 0, 0, 1, 1]);
+  }
+
+  #[test]
+  fn if_else() {
+    const SOURCE: &str = r#"
+      fn main() {
+        let x = input();
+
+        if x < 0 {
+          output(-1);
+        } else if x == 0 {
+          output(0);
+        } else {
+          output(1);
+        }
+      }
+    "#;
+
+    assert_eq!(last_output(SOURCE, &[-12]), Some(-1));
+    assert_eq!(last_output(SOURCE, &[0]), Some(0));
+    assert_eq!(last_output(SOURCE, &[8]), Some(1));
+  }
+
+  #[test]
+  fn while_loop() {
+    const SOURCE: &str = r#"
+      // count down from the input
+      fn main() {
+        let n = input();
+
+        while n > 0 {
+          output(n);
+          n = n - 1;
+        }
+      }
+    "#;
+
+    assert_eq!(outputs(SOURCE, &[5]), vec![5, 4, 3, 2, 1]);
+    assert_eq!(outputs(SOURCE, &[0]), Vec::<Word>::new());
+  }
+
+  #[test]
+  fn recursion() {
+    const SOURCE: &str = r#"
+      fn fact(n) {
+        if n < 2 {
+          return 1;
+        }
+
+        return n * fact(n - 1);
+      }
+
+      fn main() {
+        output(fact(input()));
+      }
+    "#;
+
+    assert_eq!(last_output(SOURCE, &[0]), Some(1));
+    assert_eq!(last_output(SOURCE, &[5]), Some(120));
+    assert_eq!(last_output(SOURCE, &[10]), Some(3628800));
+  }
+
+  #[test]
+  fn fibonacci() {
+    const SOURCE: &str = r#"
+      fn fib(n) {
+        if n < 2 {
+          return n;
+        }
+
+        return fib(n - 1) + fib(n - 2);
+      }
+
+      fn main() {
+        let i = 0;
+        let n = input();
+
+        while i < n {
+          output(fib(i));
+          i = i + 1;
+        }
+      }
+    "#;
+
+    assert_eq!(
+      outputs(SOURCE, &[10]),
+      vec![0, 1, 1, 2, 3, 5, 8, 13, 21, 34]
+    );
+  }
+
+  #[test]
+  fn nested_calls() {
+    const SOURCE: &str = r#"
+      fn max(a, b) {
+        if a > b {
+          return a;
+        }
+
+        return b;
+      }
+
+      fn max3(a, b, c) {
+        return max(max(a, b), c);
+      }
+
+      fn main() {
+        let a = input();
+        let b = input();
+        output(max3(a, b, max(a * b, 3)));
+      }
+    "#;
+
+    assert_eq!(last_output(SOURCE, &[2, 5]), Some(10));
+    assert_eq!(last_output(SOURCE, &[-2, 1]), Some(3));
+  }
+
+  #[test]
+  fn shadowing_and_scopes() {
+    const SOURCE: &str = r#"
+      fn main() {
+        let x = 1;
+
+        if 1 {
+          let x = 2;
+          output(x);
+        }
+
+        output(x);
+      }
+    "#;
+
+    assert_eq!(outputs(SOURCE, &[]), vec![2, 1]);
+  }
+
+  #[test]
+  fn compile_errors() {
+    assert!(compile("fn foo() {}").is_err());
+    assert!(compile("fn main(x) {}").is_err());
+    assert!(compile("fn main() { output(y); }").is_err());
+    assert!(compile("fn main() { foo(); }").is_err());
+    assert!(compile("fn f(a) {} fn main() { f(); }").is_err());
+    assert!(compile("fn main() { let x = 3 }").is_err());
+    assert!(compile("fn main() { let x = 3 / 2; }").is_err());
+  }
+
+  #[test]
+  fn short_circuit() {
+    const SOURCE: &str = r#"
+      fn side(x) {
+        output(x);
+        return x;
+      }
+
+      fn main() {
+        output(0 && side(1));
+        output(1 || side(2));
+        output(1 && side(3));
+        output(0 || side(0));
+
+        let x = input();
+        output(x || input());
+        output(x - 5 && side(4));
+        output(x && input());
+        output(x - 5 || side(6));
+      }
+    "#;
+
+    assert_eq!(
+      outputs(SOURCE, &[5, 7]),
+      vec![0, 1, 3, 1, 0, 0, 1, 0, 1, 6, 1]
+    );
+  }
+
+  #[test]
+  fn constant_overflow() {
+    assert!(compile("fn main() { output(9223372036854775807 + 1); }").is_err());
+    assert!(compile("fn main() { output(0 - 9223372036854775807 - 2); }").is_err());
+    assert!(compile("fn main() { output(4611686018427387904 * 2); }").is_err());
+    assert!(compile("fn main() { output(-(0 - 9223372036854775807 - 1)); }").is_err());
+    assert_eq!(
+      last_output("fn main() { output(0 - 9223372036854775807 - 1); }", &[]),
+      Some(Word::MIN)
+    );
+  }
+
+  #[test]
+  fn unexpected_characters() {
+    assert_eq!(
+      compile("fn main() {\n  output(1)<€; }"),
+      Err("line 2, column 13: unexpected character: '€'".to_owned())
+    );
+    assert!(compile("fn main() { output(1) é }").is_err());
+    assert_eq!(last_output("fn main() { output(1 <= 2); }", &[]), Some(1));
+  }
+}
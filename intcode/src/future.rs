@@ -0,0 +1,503 @@
+//! Asynchronous execution of intcode programs.
+//!
+//! A program can be turned into an [`Outputs`] stream with [`Program::outputs`]: inputs are
+//! awaited from an [`AsyncInput`] source whenever the program needs one, and every output is
+//! yielded as soon as it is produced. This allows intcode machines to be wired to each other or to
+//! other asynchronous code through [`channel`]s.
+//!
+//! No runtime is required: [`block_on`] is a minimal executor that is enough to drive a single
+//! future to completion. With the `futures` feature, [`Outputs`] also implements
+//! `futures_core::Stream` and any such stream of words can be used as input through [`FromStream`].
+
+use std::borrow::BorrowMut;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+use crate::{Program, Step, Word};
+
+/// An asynchronous source of input words.
+///
+/// `Poll::Ready(None)` means that the source is exhausted and will never provide inputs anymore.
+pub trait AsyncInput {
+  fn poll_input(&mut self, cx: &mut Context) -> Poll<Option<Word>>;
+}
+
+/// Input source that is always ready, built out of an iterator with [`from_iter`].
+#[derive(Clone, Debug)]
+pub struct FromIter<I>(I);
+
+/// Use an iterator as an input source.
+pub fn from_iter<I>(iter: I) -> FromIter<I::IntoIter>
+where
+  I: IntoIterator<Item = Word>,
+{
+  FromIter(iter.into_iter())
+}
+
+impl<I> AsyncInput for FromIter<I>
+where
+  I: Iterator<Item = Word>,
+{
+  fn poll_input(&mut self, _: &mut Context) -> Poll<Option<Word>> {
+    Poll::Ready(self.0.next())
+  }
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+  queue: VecDeque<Word>,
+  senders: usize,
+  waker: Option<Waker>,
+}
+
+/// Create an unbounded channel of words.
+///
+/// The receiving end is an [`AsyncInput`]; it is exhausted once all senders are dropped and the
+/// queue is empty.
+pub fn channel() -> (Sender, Receiver) {
+  let shared = Arc::new(Mutex::new(Shared {
+    senders: 1,
+    ..Shared::default()
+  }));
+
+  (
+    Sender {
+      shared: shared.clone(),
+    },
+    Receiver { shared },
+  )
+}
+
+/// Sending end of a [`channel`].
+#[derive(Debug)]
+pub struct Sender {
+  shared: Arc<Mutex<Shared>>,
+}
+
+impl Sender {
+  pub fn send(&self, w: Word) {
+    let mut shared = self.shared.lock().unwrap();
+    shared.queue.push_back(w);
+
+    if let Some(waker) = shared.waker.take() {
+      waker.wake();
+    }
+  }
+}
+
+impl Clone for Sender {
+  fn clone(&self) -> Self {
+    self.shared.lock().unwrap().senders += 1;
+
+    Sender {
+      shared: self.shared.clone(),
+    }
+  }
+}
+
+impl Drop for Sender {
+  fn drop(&mut self) {
+    let mut shared = self.shared.lock().unwrap();
+    shared.senders -= 1;
+
+    if shared.senders == 0 {
+      if let Some(waker) = shared.waker.take() {
+        waker.wake();
+      }
+    }
+  }
+}
+
+/// Receiving end of a [`channel`].
+#[derive(Debug)]
+pub struct Receiver {
+  shared: Arc<Mutex<Shared>>,
+}
+
+impl AsyncInput for Receiver {
+  fn poll_input(&mut self, cx: &mut Context) -> Poll<Option<Word>> {
+    let mut shared = self.shared.lock().unwrap();
+
+    if let Some(w) = shared.queue.pop_front() {
+      Poll::Ready(Some(w))
+    } else if shared.senders == 0 {
+      Poll::Ready(None)
+    } else {
+      shared.waker = Some(cx.waker().clone());
+      Poll::Pending
+    }
+  }
+}
+
+/// Input source built out of a `futures_core::Stream`.
+#[cfg(feature = "futures")]
+#[derive(Debug)]
+pub struct FromStream<S>(pub S);
+
+#[cfg(feature = "futures")]
+impl<S> AsyncInput for FromStream<S>
+where
+  S: futures_core::Stream<Item = Word> + Unpin,
+{
+  fn poll_input(&mut self, cx: &mut Context) -> Poll<Option<Word>> {
+    futures_core::Stream::poll_next(Pin::new(&mut self.0), cx)
+  }
+}
+
+/// Stream of the outputs of a running program.
+///
+/// `P` is either an owned [`Program`] or a mutable reference to one.
+#[derive(Debug)]
+pub struct Outputs<P, I> {
+  program: P,
+  input: I,
+  inputs: Vec<Word>,
+  done: bool,
+}
+
+impl<P, I> Outputs<P, I>
+where
+  P: BorrowMut<Program>,
+  I: AsyncInput,
+{
+  pub fn new(program: P, input: I) -> Self {
+    Outputs {
+      program,
+      input,
+      inputs: Vec::new(),
+      done: false,
+    }
+  }
+
+  /// Run the program until it outputs a word, halts or fails.
+  ///
+  /// `Poll::Pending` is returned while the program waits for its input source. Once the program
+  /// has halted or failed, `Poll::Ready(None)` is returned.
+  pub fn poll_next(&mut self, cx: &mut Context) -> Poll<Option<Result<Word, String>>> {
+    if self.done {
+      return Poll::Ready(None);
+    }
+
+    loop {
+      let step = self.program.borrow_mut().step(&mut self.inputs);
+
+      match step {
+        Ok(Step::Continue) => (),
+
+        Ok(Step::NeedInput) => match self.input.poll_input(cx) {
+          Poll::Ready(Some(w)) => self.inputs.push(w),
+
+          Poll::Ready(None) => {
+            self.done = true;
+            return Poll::Ready(Some(Err("no input: input source exhausted".to_owned())));
+          }
+
+          Poll::Pending => return Poll::Pending,
+        },
+
+        Ok(Step::Output(w)) => return Poll::Ready(Some(Ok(w))),
+
+        Ok(Step::Halt) => {
+          self.done = true;
+          return Poll::Ready(None);
+        }
+
+        Err(e) => {
+          self.done = true;
+          return Poll::Ready(Some(Err(e)));
+        }
+      }
+    }
+  }
+
+  /// Future resolving to the next output, or `None` if the program is done.
+  pub fn next_output(&mut self) -> Next<'_, P, I> {
+    Next { outputs: self }
+  }
+
+  /// Drive the program to completion and collect all of its outputs.
+  pub async fn collect(mut self) -> Result<Vec<Word>, String> {
+    let mut outputs = Vec::new();
+
+    while let Some(w) = self.next_output().await {
+      outputs.push(w?);
+    }
+
+    Ok(outputs)
+  }
+
+  pub fn into_inner(self) -> (P, I) {
+    (self.program, self.input)
+  }
+}
+
+#[cfg(feature = "futures")]
+impl<P, I> futures_core::Stream for Outputs<P, I>
+where
+  P: BorrowMut<Program> + Unpin,
+  I: AsyncInput + Unpin,
+{
+  type Item = Result<Word, String>;
+
+  fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+    Outputs::poll_next(self.get_mut(), cx)
+  }
+}
+
+/// Future returned by [`Outputs::next_output`].
+#[derive(Debug)]
+pub struct Next<'a, P, I> {
+  outputs: &'a mut Outputs<P, I>,
+}
+
+impl<'a, P, I> Future for Next<'a, P, I>
+where
+  P: BorrowMut<Program>,
+  I: AsyncInput,
+{
+  type Output = Option<Result<Word, String>>;
+
+  fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+    self.get_mut().outputs.poll_next(cx)
+  }
+}
+
+impl Program {
+  /// Stream the outputs of the program, awaiting inputs from the given source.
+  pub fn outputs<I>(&mut self, input: I) -> Outputs<&mut Self, I>
+  where
+    I: AsyncInput,
+  {
+    Outputs::new(self, input)
+  }
+
+  /// Run until the program halts, awaiting inputs from the given source, and return all outputs.
+  pub async fn run_async<I>(&mut self, input: I) -> Result<Vec<Word>, String>
+  where
+    I: AsyncInput,
+  {
+    self.outputs(input).collect().await
+  }
+}
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+  fn wake(self: Arc<Self>) {
+    self.0.unpark();
+  }
+}
+
+/// Run a future to completion on the current thread, parking it while the future is pending.
+pub fn block_on<F>(future: F) -> F::Output
+where
+  F: Future,
+{
+  let mut future = Box::pin(future);
+  let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+  let mut cx = Context::from_waker(&waker);
+
+  loop {
+    match future.as_mut().poll(&mut cx) {
+      Poll::Ready(output) => break output,
+      Poll::Pending => thread::park(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Poll all futures in turn until they all complete.
+  async fn join_all<F>(futures: Vec<F>) -> Vec<F::Output>
+  where
+    F: Future + Unpin,
+    F::Output: Unpin,
+  {
+    struct JoinAll<F: Future>(Vec<(F, Option<F::Output>)>);
+
+    impl<F> Future for JoinAll<F>
+    where
+      F: Future + Unpin,
+      F::Output: Unpin,
+    {
+      type Output = Vec<F::Output>;
+
+      fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        for (future, output) in &mut this.0 {
+          if output.is_none() {
+            if let Poll::Ready(o) = Pin::new(future).poll(cx) {
+              *output = Some(o);
+            }
+          }
+        }
+
+        if this.0.iter().all(|(_, output)| output.is_some()) {
+          Poll::Ready(this.0.drain(..).map(|(_, o)| o.unwrap()).collect())
+        } else {
+          Poll::Pending
+        }
+      }
+    }
+
+    JoinAll(futures.into_iter().map(|f| (f, None)).collect()).await
+  }
+
+  #[test]
+  fn run_from_iter() {
+    // output the input if it’s 8, 0 otherwise… twice
+    let mut program = Program::from_str("3,9,8,9,10,9,4,9,99,-1,8").unwrap();
+
+    assert_eq!(block_on(program.run_async(from_iter(vec![8]))), Ok(vec![1]));
+    assert_eq!(
+      block_on(
+        Program::from_str("3,9,8,9,10,9,4,9,99,-1,8")
+          .unwrap()
+          .run_async(from_iter(vec![3]))
+      ),
+      Ok(vec![0])
+    );
+  }
+
+  #[test]
+  fn exhausted_input() {
+    let mut program = Program::from_str("3,0,99").unwrap();
+    assert!(block_on(program.run_async(from_iter(vec![]))).is_err());
+  }
+
+  #[test]
+  fn input_from_another_thread() {
+    // echo two inputs back
+    let mut program = Program::from_str("3,0,4,0,3,0,4,0,99").unwrap();
+    let (tx, rx) = channel();
+
+    let feeder = thread::spawn(move || {
+      for w in &[12, 34] {
+        thread::sleep(std::time::Duration::from_millis(10));
+        tx.send(*w);
+      }
+    });
+
+    assert_eq!(block_on(program.run_async(rx)), Ok(vec![12, 34]));
+    feeder.join().unwrap();
+  }
+
+  #[test]
+  fn stream_outputs() {
+    let mut program = Program::from_str("104,1,104,2,104,3,99").unwrap();
+    let mut outputs = program.outputs(from_iter(vec![]));
+
+    assert_eq!(block_on(outputs.next_output()), Some(Ok(1)));
+    assert_eq!(block_on(outputs.next_output()), Some(Ok(2)));
+    assert_eq!(block_on(outputs.next_output()), Some(Ok(3)));
+    assert_eq!(block_on(outputs.next_output()), None);
+    assert_eq!(block_on(outputs.next_output()), None);
+  }
+
+  #[test]
+  fn feedback_loop() {
+    const AMPLIFIER: &str =
+      "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5";
+    const PHASES: [Word; 5] = [9, 8, 7, 6, 5];
+
+    let channels: Vec<_> = (0..5).map(|_| channel()).collect();
+    let (senders, receivers): (Vec<_>, Vec<_>) = channels.into_iter().unzip();
+
+    for (sender, phase) in senders.iter().zip(&PHASES) {
+      sender.send(*phase);
+    }
+    senders[0].send(0);
+
+    let amplifiers = receivers
+      .into_iter()
+      .enumerate()
+      .map(|(i, rx)| {
+        let next = senders[(i + 1) % 5].clone();
+        let program = Program::from_str(AMPLIFIER).unwrap();
+
+        Box::pin(async move {
+          let mut outputs = Outputs::new(program, rx);
+          let mut last = None;
+
+          while let Some(w) = outputs.next_output().await {
+            let w = w?;
+            next.send(w);
+            last = Some(w);
+          }
+
+          Ok::<_, String>(last)
+        })
+      })
+      .collect();
+
+    drop(senders);
+
+    let signals = block_on(join_all(amplifiers));
+    assert_eq!(signals[4], Ok(Some(139629729)));
+  }
+
+  #[cfg(feature = "futures")]
+  #[test]
+  fn futures_stream() {
+    use futures_core::Stream;
+
+    /// Stream of words that is pending once before each of them.
+    struct Words {
+      words: VecDeque<Word>,
+      ready: bool,
+    }
+
+    impl Stream for Words {
+      type Item = Word;
+
+      fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Word>> {
+        let this = self.get_mut();
+
+        if this.ready {
+          this.ready = false;
+          Poll::Ready(this.words.pop_front())
+        } else {
+          this.ready = true;
+          cx.waker().wake_by_ref();
+          Poll::Pending
+        }
+      }
+    }
+
+    /// Future resolving to the next item of a stream.
+    struct NextItem<'a, S>(&'a mut S);
+
+    impl<'a, S> Future for NextItem<'a, S>
+    where
+      S: Stream + Unpin,
+    {
+      type Output = Option<S::Item>;
+
+      fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        Pin::new(&mut *self.get_mut().0).poll_next(cx)
+      }
+    }
+
+    // echo two inputs back
+    let program = Program::from_str("3,0,4,0,3,0,4,0,99").unwrap();
+    let input = FromStream(Words {
+      words: vec![12, 34].into(),
+      ready: false,
+    });
+    let mut outputs = Outputs::new(program, input);
+    let mut collected = Vec::new();
+
+    while let Some(w) = block_on(NextItem(&mut outputs)) {
+      collected.push(w);
+    }
+
+    assert_eq!(collected, vec![Ok(12), Ok(34)]);
+  }
+}
@@ -1,4 +1,13 @@
 use std::convert::TryFrom;
+use std::fmt;
+
+pub mod compiler;
+pub mod future;
+pub mod replay;
+pub mod selfmod;
+
+use crate::replay::{Event, Replay};
+use crate::selfmod::{Policy, SelfModification, Tracker};
 
 const DEFAULT_MEMORY_SIZE: usize = 10000;
 
@@ -6,11 +15,13 @@ pub type IP = usize;
 pub type IPOffset = isize;
 pub type Word = i64;
 
-#[derive(Debug)]
 pub struct Program {
   memory: Vec<Word>,
   ip: IP,
   rel_base: IPOffset,
+  steps: u64,
+  recording: Option<Replay>,
+  tracker: Option<Tracker>,
 }
 
 impl Program {
@@ -23,9 +34,13 @@ impl Program {
       memory,
       ip,
       rel_base,
+      steps: 0,
+      recording: None,
+      tracker: None,
     }
   }
 
+  #[allow(clippy::should_implement_trait)]
   pub fn from_str<S>(input: S) -> Result<Self, String>
   where
     S: AsRef<str>,
@@ -46,18 +61,136 @@ impl Program {
       memory,
       ip,
       rel_base,
+      steps: 0,
+      recording: None,
+      tracker: None,
     })
   }
 
+  /// Create a program out of already decoded words, such as the output of the
+  /// [`compiler`](compiler/index.html).
+  pub fn from_words(words: &[Word]) -> Self {
+    let mut memory = words.to_owned();
+    let ip = 0;
+    let rel_base = 0;
+
+    memory.resize(memory.len().max(DEFAULT_MEMORY_SIZE), 0);
+
+    Program {
+      memory,
+      ip,
+      rel_base,
+      steps: 0,
+      recording: None,
+      tracker: None,
+    }
+  }
+
   pub fn mem_size(&self) -> usize {
     self.memory.len()
   }
 
+  /// Current instruction pointer.
+  pub fn ip(&self) -> IP {
+    self.ip
+  }
+
+  /// Move the instruction pointer.
+  pub fn set_ip(&mut self, ip: IP) {
+    self.ip = ip;
+  }
+
+  /// Current relative base.
+  pub fn rel_base(&self) -> IPOffset {
+    self.rel_base
+  }
+
+  /// Number of instructions executed so far.
+  pub fn steps(&self) -> u64 {
+    self.steps
+  }
+
+  /// Start recording every input consumed and output produced into a [`Replay`].
+  ///
+  /// Any previous recording is discarded.
+  pub fn start_recording(&mut self) {
+    self.recording = Some(Replay::new());
+  }
+
+  /// Stop recording and return what was recorded, if anything.
+  pub fn take_recording(&mut self) -> Option<Replay> {
+    self.recording.take()
+  }
+
+  /// Events recorded so far, if recording.
+  pub fn recording(&self) -> Option<&Replay> {
+    self.recording.as_ref()
+  }
+
+  fn record(&mut self, event: Event) {
+    if let Some(ref mut replay) = self.recording {
+      replay.push(event);
+    }
+  }
+
+  /// Track writes to addresses that were already executed.
+  ///
+  /// Any previous tracking information is discarded.
+  pub fn track_self_modification(&mut self, policy: Policy) {
+    self.tracker = Some(Tracker::new(policy, self.memory.len()));
+  }
+
+  /// Every write to an already executed address since tracking started.
+  pub fn self_modifications(&self) -> &[SelfModification] {
+    self.tracker.as_ref().map_or(&[], Tracker::writes)
+  }
+
+  /// Summary of the self-modifying sites, if tracking.
+  pub fn self_modification_report(&self) -> Option<selfmod::Report> {
+    self
+      .tracker
+      .as_ref()
+      .map(|tracker| selfmod::Report::new(self, tracker.writes()))
+  }
+
+  /// View of the whole memory.
+  pub fn memory(&self) -> &[Word] {
+    &self.memory
+  }
+
+  /// Disassemble the instruction at the given address.
+  ///
+  /// Return its textual representation along with the number of words it spans.
+  pub fn disassemble(&self, addr: usize) -> Result<(String, usize), String> {
+    let w = self.read(addr)?;
+    let opcode = extract_op_code(w)?;
+    let modes = opcode.modes();
+    let mut text = opcode.mnemonic().to_owned();
+
+    for (i, mode) in modes.iter().enumerate() {
+      let operand = self.read(addr + 1 + i)?;
+      let sep = if i == 0 { " " } else { ", " };
+
+      text += &match mode {
+        ParamMode::Position => format!("{}[{}]", sep, operand),
+        ParamMode::Immediate => format!("{}{}", sep, operand),
+        ParamMode::Relative => format!("{}rb[{:+}]", sep, operand),
+      };
+    }
+
+    Ok((text, 1 + modes.len()))
+  }
+
   pub fn mimick(&mut self, other: &Self) {
     self.memory.clear();
     self.memory.extend_from_slice(&other.memory);
     self.ip = 0;
     self.rel_base = 0;
+    self.steps = 0;
+
+    if let Some(ref mut tracker) = self.tracker {
+      *tracker = Tracker::new(tracker.policy(), self.memory.len());
+    }
   }
 
   pub fn is_halted(&self) -> bool {
@@ -90,6 +223,21 @@ impl Program {
     Ok(())
   }
 
+  /// Write performed by the current instruction, checked for self-modification if tracking.
+  fn store(&mut self, i: usize, w: Word) -> Result<(), String> {
+    if let Some(ref mut tracker) = self.tracker {
+      tracker.check_write(SelfModification {
+        at: self.steps,
+        writer: self.ip,
+        target: i,
+        old: self.memory.get(i).copied().unwrap_or(0),
+        new: w,
+      })?;
+    }
+
+    self.write(i, w)
+  }
+
   /// Ensure the IP will not overflow memory.
   fn guard_memory_ip(&self, offset: IPOffset) -> Result<(), String> {
     let len = self.memory.len();
@@ -159,7 +307,7 @@ impl Program {
 
     let output = f(op1, op2);
 
-    self.write(output_idx, output)?;
+    self.store(output_idx, output)?;
 
     Ok(IPControl::Increase(4))
   }
@@ -177,7 +325,7 @@ impl Program {
       return Err(format!("cannot store input at {}: out of bounds", addr));
     }
 
-    self.write(addr, inputs[0])?;
+    self.store(addr, inputs[0])?;
 
     Ok(IPControl::Increase(2))
   }
@@ -226,7 +374,7 @@ impl Program {
     let op2 = self.read_operand(2, mode_2)?;
     let output_idx = self.read_addr_operand(3, mode_3)? as usize;
 
-    self.write(output_idx, pred(op1, op2) as Word)?;
+    self.store(output_idx, pred(op1, op2) as Word)?;
 
     Ok(IPControl::Increase(4))
   }
@@ -278,54 +426,90 @@ impl Program {
     }
 
     loop {
-      let opcode = extract_op_code(self.memory[self.ip])?;
+      match self.step(&mut inputs)? {
+        Step::Continue => (),
 
-      let ip_ctrl = match opcode {
-        OpCode::Add(mode_1, mode_2, mode_3) => {
-          self.perform_op(mode_1, mode_2, mode_3, |a, b| a + b)?
-        }
+        Step::NeedInput => return Err("no input".to_owned()),
 
-        OpCode::Mult(mode_1, mode_2, mode_3) => {
-          self.perform_op(mode_1, mode_2, mode_3, |a, b| a * b)?
+        Step::Output(out) => {
+          output = Some(out);
+          return Ok(Suspended::Running { inputs, output });
         }
 
-        OpCode::GetInput(mode) => {
-          let ip_ctrl = self.perform_get_input(&inputs, mode)?;
-          inputs.swap_remove(0);
-          ip_ctrl
-        }
+        Step::Halt => break,
+      }
+    }
 
-        OpCode::Output(mode) => {
-          let mut out = 0;
-          let ip_ctrl = self.perform_output(&mut out, mode)?;
-          output = Some(out);
+    Ok(Suspended::Halted { output })
+  }
 
-          self.update_ip(ip_ctrl);
+  /// Execute a single instruction.
+  ///
+  /// If the instruction requires an input and none is available, nothing is executed and
+  /// `Step::NeedInput` is returned, so that the caller can provide one and step again.
+  fn step(&mut self, inputs: &mut Vec<Word>) -> Result<Step, String> {
+    let opcode = extract_op_code(self.memory[self.ip])?;
 
-          return Ok(Suspended::Running { inputs, output });
-        }
+    if let Some(ref mut tracker) = self.tracker {
+      tracker.execute(self.ip, 1 + opcode.modes().len());
+    }
 
-        OpCode::JumpIfTrue(mode_1, mode_2) => self.perform_jump(mode_1, mode_2, true)?,
+    let ip_ctrl = match opcode {
+      OpCode::Add(mode_1, mode_2, mode_3) => {
+        self.perform_op(mode_1, mode_2, mode_3, |a, b| a + b)?
+      }
 
-        OpCode::JumpIfFalse(mode_1, mode_2) => self.perform_jump(mode_1, mode_2, false)?,
+      OpCode::Mult(mode_1, mode_2, mode_3) => {
+        self.perform_op(mode_1, mode_2, mode_3, |a, b| a * b)?
+      }
 
-        OpCode::IfLT(mode_1, mode_2, mode_3) => {
-          self.perform_conditional(mode_1, mode_2, mode_3, |a, b| a < b)?
-        }
+      OpCode::GetInput(_) if inputs.is_empty() => return Ok(Step::NeedInput),
 
-        OpCode::IfEQ(mode_1, mode_2, mode_3) => {
-          self.perform_conditional(mode_1, mode_2, mode_3, |a, b| a == b)?
-        }
+      OpCode::GetInput(mode) => {
+        let ip_ctrl = self.perform_get_input(inputs, mode)?;
+        let input = inputs.remove(0);
+        self.record(Event::Input {
+          at: self.steps,
+          value: input,
+        });
+        ip_ctrl
+      }
 
-        OpCode::AdjustRelBase(mode) => self.perform_adjust_rel_base(mode)?,
+      OpCode::Output(mode) => {
+        let mut out = 0;
+        let ip_ctrl = self.perform_output(&mut out, mode)?;
 
-        OpCode::Halt => break,
-      };
+        self.record(Event::Output {
+          at: self.steps,
+          value: out,
+        });
+        self.steps += 1;
+        self.update_ip(ip_ctrl);
 
-      self.update_ip(ip_ctrl);
-    }
+        return Ok(Step::Output(out));
+      }
 
-    Ok(Suspended::Halted { output })
+      OpCode::JumpIfTrue(mode_1, mode_2) => self.perform_jump(mode_1, mode_2, true)?,
+
+      OpCode::JumpIfFalse(mode_1, mode_2) => self.perform_jump(mode_1, mode_2, false)?,
+
+      OpCode::IfLT(mode_1, mode_2, mode_3) => {
+        self.perform_conditional(mode_1, mode_2, mode_3, |a, b| a < b)?
+      }
+
+      OpCode::IfEQ(mode_1, mode_2, mode_3) => {
+        self.perform_conditional(mode_1, mode_2, mode_3, |a, b| a == b)?
+      }
+
+      OpCode::AdjustRelBase(mode) => self.perform_adjust_rel_base(mode)?,
+
+      OpCode::Halt => return Ok(Step::Halt),
+    };
+
+    self.steps += 1;
+    self.update_ip(ip_ctrl);
+
+    Ok(Step::Continue)
   }
 
   pub fn rerun(&mut self, suspended: Suspended) -> Result<Suspended, String> {
@@ -344,6 +528,112 @@ impl Program {
   }
 }
 
+/// Only the non-zero regions of memory are shown, along with their start address.
+impl fmt::Debug for Program {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("Program")
+      .field("ip", &self.ip)
+      .field("rel_base", &self.rel_base)
+      .field("steps", &self.steps)
+      .field("mem_size", &self.memory.len())
+      .field("memory", &NonZeroRegions(&self.memory))
+      .finish()
+  }
+}
+
+struct NonZeroRegions<'a>(&'a [Word]);
+
+impl<'a> fmt::Debug for NonZeroRegions<'a> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let mut map = f.debug_map();
+    let mut i = 0;
+
+    while i < self.0.len() {
+      if self.0[i] == 0 {
+        i += 1;
+        continue;
+      }
+
+      let start = i;
+      while i < self.0.len() && self.0[i] != 0 {
+        i += 1;
+      }
+
+      map.entry(&start, &&self.0[start..i]);
+    }
+
+    map.finish()
+  }
+}
+
+/// Number of raw words shown before the instruction pointer.
+const DISPLAY_WORDS_BEFORE: usize = 4;
+
+/// Number of instructions disassembled from the instruction pointer.
+const DISPLAY_INSTRUCTIONS: usize = 3;
+
+/// Show the registers, the raw words preceding the instruction pointer and the next few
+/// disassembled instructions, the current one being marked with `>`.
+///
+/// `<halted>` is only shown once the instruction pointer went past the end of memory; a program
+/// stopped on a halt instruction shows it as its current instruction, `> hlt`.
+impl fmt::Display for Program {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    writeln!(f, "ip={} rel_base={}", self.ip, self.rel_base)?;
+
+    if self.is_halted() {
+      return write!(f, "  <halted>");
+    }
+
+    let start = self.ip.saturating_sub(DISPLAY_WORDS_BEFORE);
+    for addr in start..self.ip {
+      writeln!(f, "  {:>6}: {}", addr, self.memory[addr])?;
+    }
+
+    let mut addr = self.ip;
+    for i in 0..DISPLAY_INSTRUCTIONS {
+      if addr >= self.memory.len() {
+        break;
+      }
+
+      let marker = if i == 0 { '>' } else { ' ' };
+
+      match self.disassemble(addr) {
+        Ok((text, len)) => {
+          let words: Vec<_> = self.memory[addr..(addr + len)]
+            .iter()
+            .map(Word::to_string)
+            .collect();
+          write!(
+            f,
+            "{} {:>6}: {:<24} ; {}",
+            marker,
+            addr,
+            text,
+            words.join(",")
+          )?;
+          addr += len;
+        }
+
+        Err(_) => {
+          write!(
+            f,
+            "{} {:>6}: {:<24} ; {}",
+            marker, addr, "???", self.memory[addr]
+          )?;
+          addr += 1;
+        }
+      }
+
+      if i + 1 < DISPLAY_INSTRUCTIONS {
+        writeln!(f)?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
 /// A suspended program.
 ///
 /// A suspended program can be re-run or killed.
@@ -368,6 +658,15 @@ impl Suspended {
   }
 }
 
+/// Outcome of executing a single instruction.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Step {
+  Continue,
+  NeedInput,
+  Output(Word),
+  Halt,
+}
+
 /// Instruction pointer control.
 ///
 /// `IPControl::Increase` is just the normal flow (the IP increases after each instruction).
@@ -393,6 +692,37 @@ enum OpCode {
   Halt, // the world makes no sense
 }
 
+impl OpCode {
+  fn mnemonic(&self) -> &'static str {
+    match *self {
+      OpCode::Add(..) => "add",
+      OpCode::Mult(..) => "mul",
+      OpCode::GetInput(_) => "in",
+      OpCode::Output(_) => "out",
+      OpCode::JumpIfTrue(..) => "jt",
+      OpCode::JumpIfFalse(..) => "jf",
+      OpCode::IfLT(..) => "lt",
+      OpCode::IfEQ(..) => "eq",
+      OpCode::AdjustRelBase(_) => "arb",
+      OpCode::Halt => "hlt",
+    }
+  }
+
+  fn modes(&self) -> Vec<ParamMode> {
+    match *self {
+      OpCode::Add(a, b, c)
+      | OpCode::Mult(a, b, c)
+      | OpCode::IfLT(a, b, c)
+      | OpCode::IfEQ(a, b, c) => {
+        vec![a, b, c]
+      }
+      OpCode::JumpIfTrue(a, b) | OpCode::JumpIfFalse(a, b) => vec![a, b],
+      OpCode::GetInput(a) | OpCode::Output(a) | OpCode::AdjustRelBase(a) => vec![a],
+      OpCode::Halt => Vec::new(),
+    }
+  }
+}
+
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 enum ParamMode {
   Position,
@@ -489,3 +819,87 @@ fn extract_op_code(w: Word) -> Result<OpCode, String> {
     x => Err(format!("unknown opcode: {} ({})", x, w)),
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn accessors() {
+    let mut program = Program::from_str("1,9,10,3,2,3,11,0,99,30,40,50").unwrap();
+
+    assert_eq!(program.ip(), 0);
+    assert_eq!(program.rel_base(), 0);
+    assert_eq!(&program.memory()[0..4], &[1, 9, 10, 3]);
+
+    program.run(&[]).unwrap();
+    assert_eq!(program.ip(), 8);
+    assert_eq!(program.memory()[0], 3500);
+
+    program.set_ip(4);
+    assert_eq!(program.ip(), 4);
+  }
+
+  #[test]
+  fn inputs_in_order() {
+    // echo three inputs, suspending after each output
+    let mut program = Program::from_str("3,0,4,0,3,0,4,0,3,0,4,0,99").unwrap();
+    let mut suspended = program.run_suspended(&[1, 2, 3]).unwrap();
+    let mut outputs = Vec::new();
+
+    while let Suspended::Running { output, .. } = suspended {
+      outputs.extend(output);
+      suspended = program.rerun(suspended).unwrap();
+    }
+
+    assert_eq!(outputs, vec![1, 2, 3]);
+  }
+
+  #[test]
+  fn disassemble() {
+    let program = Program::from_str("1002,4,3,4,99,109,-3,21101,1,2,5").unwrap();
+
+    assert_eq!(
+      program.disassemble(0).unwrap(),
+      ("mul [4], 3, [4]".to_owned(), 4)
+    );
+    assert_eq!(program.disassemble(4).unwrap(), ("hlt".to_owned(), 1));
+    assert_eq!(program.disassemble(5).unwrap(), ("arb -3".to_owned(), 2));
+    assert_eq!(
+      program.disassemble(7).unwrap(),
+      ("add 1, 2, rb[+5]".to_owned(), 4)
+    );
+    assert!(program.disassemble(6).is_err());
+  }
+
+  #[test]
+  fn compact_debug() {
+    let mut program = Program::from_str("1,0,0,0,99").unwrap();
+    program.write(100, 7).unwrap();
+    program.write(101, 8).unwrap();
+
+    assert_eq!(
+      format!("{:?}", program),
+      "Program { ip: 0, rel_base: 0, steps: 0, mem_size: 10000, memory: {0: [1], 4: [99], 100: [7, 8]} }"
+    );
+  }
+
+  #[test]
+  fn display() {
+    let mut program = Program::from_str("1,0,0,0,1002,4,3,4,99").unwrap();
+    program.set_ip(4);
+
+    let expected = [
+      "ip=4 rel_base=0",
+      "       0: 1",
+      "       1: 0",
+      "       2: 0",
+      "       3: 0",
+      ">      4: mul [4], 3, [4]          ; 1002,4,3,4",
+      "       8: hlt                      ; 99",
+      "       9: ???                      ; 0",
+    ];
+
+    assert_eq!(program.to_string(), expected.join("\n"));
+  }
+}
@@ -0,0 +1,329 @@
+//! Deterministic replays of intcode sessions.
+//!
+//! A [`Replay`] is the ordered list of inputs consumed and outputs produced by a [`Program`], each
+//! tagged with the number of instructions executed before it. Replays are recorded with
+//! [`Program::start_recording`] and can be stored as text, one event per line:
+//!
+//! ```text
+//! # comments and blank lines are ignored
+//! in 0 5
+//! out 12 25
+//! ```
+//!
+//! Re-running a program against a replay with [`Replay::verify`] feeds back the recorded inputs and
+//! reports the first event that does not match.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::{Program, Suspended, Word};
+
+/// An input consumed or an output produced, at a given instruction count.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Event {
+  Input { at: u64, value: Word },
+  Output { at: u64, value: Word },
+}
+
+impl fmt::Display for Event {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      Event::Input { at, value } => write!(f, "in {} {}", at, value),
+      Event::Output { at, value } => write!(f, "out {} {}", at, value),
+    }
+  }
+}
+
+impl FromStr for Event {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut fields = s.split_whitespace();
+    let kind = fields.next().ok_or_else(|| "empty event".to_owned())?;
+    let at = fields
+      .next()
+      .ok_or_else(|| "missing instruction count".to_owned())?
+      .parse()
+      .map_err(|e| format!("cannot parse instruction count: {}", e))?;
+    let value = fields
+      .next()
+      .ok_or_else(|| "missing value".to_owned())?
+      .parse()
+      .map_err(|e| format!("cannot parse value: {}", e))?;
+
+    if let Some(extra) = fields.next() {
+      return Err(format!("unexpected trailing data: {}", extra));
+    }
+
+    match kind {
+      "in" => Ok(Event::Input { at, value }),
+      "out" => Ok(Event::Output { at, value }),
+      _ => Err(format!("unknown event kind: {}", kind)),
+    }
+  }
+}
+
+/// A recorded intcode session.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Replay {
+  events: Vec<Event>,
+}
+
+impl Replay {
+  pub fn new() -> Self {
+    Replay::default()
+  }
+
+  pub fn events(&self) -> &[Event] {
+    &self.events
+  }
+
+  pub fn push(&mut self, event: Event) {
+    self.events.push(event);
+  }
+
+  /// All recorded inputs, in order.
+  pub fn inputs(&self) -> impl Iterator<Item = Word> + '_ {
+    self.events.iter().filter_map(|event| match *event {
+      Event::Input { value, .. } => Some(value),
+      _ => None,
+    })
+  }
+
+  /// All recorded outputs, in order.
+  pub fn outputs(&self) -> impl Iterator<Item = Word> + '_ {
+    self.events.iter().filter_map(|event| match *event {
+      Event::Output { value, .. } => Some(value),
+      _ => None,
+    })
+  }
+
+  pub fn load<P>(path: P) -> Result<Self, String>
+  where
+    P: AsRef<Path>,
+  {
+    let path = path.as_ref();
+    let content = fs::read_to_string(path)
+      .map_err(|e| format!("cannot read replay {}: {}", path.display(), e))?;
+
+    content.parse()
+  }
+
+  pub fn save<P>(&self, path: P) -> Result<(), String>
+  where
+    P: AsRef<Path>,
+  {
+    let path = path.as_ref();
+    fs::write(path, self.to_string())
+      .map_err(|e| format!("cannot write replay {}: {}", path.display(), e))
+  }
+
+  /// Run a program, feeding it the recorded inputs, and check that it produces the exact same
+  /// events.
+  ///
+  /// The program must be in the same initial state as when the replay was recorded.
+  pub fn verify(&self, program: &mut Program) -> Result<(), Divergence> {
+    let inputs: Vec<_> = self.inputs().collect();
+    let mut checked = 0;
+
+    program.start_recording();
+    let mut suspended = program.run_suspended(&inputs);
+
+    let result = loop {
+      let events = program.recording().map_or(&[][..], Replay::events);
+
+      // compare everything that happened since the last suspension
+      if let Some(divergence) = self.compare(events, checked) {
+        break Err(divergence);
+      }
+      checked = events.len();
+
+      match suspended {
+        Ok(Suspended::Running { .. }) => {
+          suspended = program.rerun(suspended.unwrap());
+        }
+
+        Ok(Suspended::Halted { .. }) => {
+          if checked < self.events.len() {
+            break Err(Divergence {
+              index: checked,
+              expected: Some(self.events[checked]),
+              found: None,
+              error: Some("program halted".to_owned()),
+            });
+          }
+
+          break Ok(());
+        }
+
+        Err(e) => {
+          break Err(Divergence {
+            index: checked,
+            expected: self.events.get(checked).copied(),
+            found: None,
+            error: Some(e),
+          });
+        }
+      }
+    };
+
+    program.take_recording();
+    result
+  }
+
+  fn compare(&self, events: &[Event], from: usize) -> Option<Divergence> {
+    (from..events.len()).find_map(|index| {
+      let expected = self.events.get(index).copied();
+
+      if expected == Some(events[index]) {
+        None
+      } else {
+        Some(Divergence {
+          index,
+          expected,
+          found: Some(events[index]),
+          error: None,
+        })
+      }
+    })
+  }
+}
+
+impl fmt::Display for Replay {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    writeln!(f, "# intcode replay")?;
+
+    for event in &self.events {
+      writeln!(f, "{}", event)?;
+    }
+
+    Ok(())
+  }
+}
+
+impl FromStr for Replay {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let events = s
+      .lines()
+      .enumerate()
+      .map(|(i, line)| (i, line.trim()))
+      .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+      .map(|(i, line)| line.parse().map_err(|e| format!("line {}: {}", i + 1, e)))
+      .collect::<Result<_, _>>()?;
+
+    Ok(Replay { events })
+  }
+}
+
+/// First point at which a program stopped behaving as recorded.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Divergence {
+  /// Index of the diverging event in the replay.
+  pub index: usize,
+  /// Recorded event, if the replay had one at that index.
+  pub expected: Option<Event>,
+  /// Event produced by the program instead, if any.
+  pub found: Option<Event>,
+  /// Error or premature halt that prevented the program from producing the expected event.
+  pub error: Option<String>,
+}
+
+impl fmt::Display for Divergence {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "divergence at event {}: expected ", self.index)?;
+
+    match self.expected {
+      Some(event) => write!(f, "{}", event)?,
+      None => write!(f, "nothing")?,
+    }
+
+    match (self.found, &self.error) {
+      (Some(event), _) => write!(f, ", found {}", event),
+      (None, Some(e)) => write!(f, ", found error: {}", e),
+      (None, None) => write!(f, ", found nothing"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // add the two inputs, output the sum, then output whether the sum is below 11
+  const ADDER: &str = "3,17,3,18,1,17,18,19,4,19,1007,19,11,20,4,20,99";
+
+  fn record(inputs: &[Word]) -> Replay {
+    let mut program = Program::from_str(ADDER).unwrap();
+    program.start_recording();
+    program.run(inputs).unwrap();
+    program.take_recording().unwrap()
+  }
+
+  #[test]
+  fn record_events() {
+    let replay = record(&[4, 9]);
+
+    assert_eq!(
+      replay.events(),
+      &[
+        Event::Input { at: 0, value: 4 },
+        Event::Input { at: 1, value: 9 },
+        Event::Output { at: 3, value: 13 },
+        Event::Output { at: 5, value: 0 },
+      ]
+    );
+  }
+
+  #[test]
+  fn text_roundtrip() {
+    let replay = record(&[4, 9]);
+    let text = replay.to_string();
+
+    assert_eq!(
+      text,
+      "# intcode replay\nin 0 4\nin 1 9\nout 3 13\nout 5 0\n"
+    );
+    assert_eq!(text.parse::<Replay>().unwrap(), replay);
+    assert!("in 0".parse::<Replay>().is_err());
+    assert!("jump 0 1".parse::<Replay>().is_err());
+  }
+
+  #[test]
+  fn verify_ok() {
+    let replay = record(&[4, 9]);
+    let mut program = Program::from_str(ADDER).unwrap();
+
+    assert_eq!(replay.verify(&mut program), Ok(()));
+    assert!(program.recording().is_none());
+  }
+
+  #[test]
+  fn verify_divergence() {
+    let replay = record(&[4, 9]);
+
+    // patch the comparison so that the second output differs
+    let mut program = Program::from_str(ADDER).unwrap();
+    program.write(12, 20).unwrap();
+
+    let divergence = replay.verify(&mut program).unwrap_err();
+    assert_eq!(divergence.index, 3);
+    assert_eq!(divergence.expected, Some(Event::Output { at: 5, value: 0 }));
+    assert_eq!(divergence.found, Some(Event::Output { at: 5, value: 1 }));
+  }
+
+  #[test]
+  fn verify_missing_events() {
+    let mut replay = record(&[4, 9]);
+    replay.push(Event::Output { at: 6, value: 42 });
+
+    let mut program = Program::from_str(ADDER).unwrap();
+    let divergence = replay.verify(&mut program).unwrap_err();
+    assert_eq!(divergence.index, 4);
+    assert_eq!(divergence.found, None);
+    assert!(divergence.error.is_some());
+  }
+}
@@ -0,0 +1,240 @@
+//! Self-modification tracking.
+//!
+//! Intcode programs can overwrite their own instructions. When tracking is enabled with
+//! [`Program::track_self_modification`], every address decoded as part of an executed instruction
+//! is remembered, and any instruction writing to one of those addresses is reported — or turned
+//! into an error, depending on the [`Policy`].
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::{Program, Word, IP};
+
+/// What to do when a program writes to an already executed address.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Policy {
+  /// Record the write and carry on.
+  Report,
+  /// Record the write and fail the instruction.
+  Error,
+}
+
+/// A single write to an already executed address.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SelfModification {
+  /// Instruction count at which the write happened.
+  pub at: u64,
+  /// Address of the writing instruction.
+  pub writer: IP,
+  /// Overwritten address.
+  pub target: usize,
+  pub old: Word,
+  pub new: Word,
+}
+
+impl fmt::Display for SelfModification {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "step {}: instruction at {} overwrote executed address {} ({} -> {})",
+      self.at, self.writer, self.target, self.old, self.new
+    )
+  }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Tracker {
+  policy: Policy,
+  executed: Vec<bool>,
+  writes: Vec<SelfModification>,
+}
+
+impl Tracker {
+  pub(crate) fn new(policy: Policy, mem_size: usize) -> Self {
+    Tracker {
+      policy,
+      executed: vec![false; mem_size],
+      writes: Vec::new(),
+    }
+  }
+
+  pub(crate) fn policy(&self) -> Policy {
+    self.policy
+  }
+
+  /// Mark the words of an instruction as executed.
+  pub(crate) fn execute(&mut self, ip: IP, len: usize) {
+    let end = (ip + len).min(self.executed.len());
+
+    for executed in &mut self.executed[ip..end] {
+      *executed = true;
+    }
+  }
+
+  /// Check a write performed by an instruction.
+  pub(crate) fn check_write(&mut self, write: SelfModification) -> Result<(), String> {
+    if !self.executed.get(write.target).copied().unwrap_or(false) {
+      return Ok(());
+    }
+
+    self.writes.push(write);
+
+    match self.policy {
+      Policy::Report => Ok(()),
+      Policy::Error => Err(format!("self-modification: {}", write)),
+    }
+  }
+
+  pub(crate) fn writes(&self) -> &[SelfModification] {
+    &self.writes
+  }
+}
+
+/// A site of self-modification: an instruction writing to an executed address.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Site {
+  pub writer: IP,
+  pub target: usize,
+  /// Number of writes from `writer` to `target`.
+  pub count: usize,
+  /// Instruction count of the first write.
+  pub first: u64,
+  /// Disassembly of the writing instruction, if it still decodes.
+  pub instruction: Option<String>,
+}
+
+/// Summary of all self-modifying sites of a run.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Report {
+  pub sites: Vec<Site>,
+}
+
+impl Report {
+  pub(crate) fn new(program: &Program, writes: &[SelfModification]) -> Self {
+    let mut sites = BTreeMap::new();
+
+    for write in writes {
+      sites
+        .entry((write.writer, write.target))
+        .and_modify(|(count, _)| *count += 1)
+        .or_insert((1, write.at));
+    }
+
+    let sites = sites
+      .into_iter()
+      .map(|((writer, target), (count, first))| Site {
+        writer,
+        target,
+        count,
+        first,
+        instruction: program.disassemble(writer).ok().map(|(text, _)| text),
+      })
+      .collect();
+
+    Report { sites }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.sites.is_empty()
+  }
+}
+
+impl fmt::Display for Report {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    if self.sites.is_empty() {
+      return write!(f, "no self-modification");
+    }
+
+    for (i, site) in self.sites.iter().enumerate() {
+      if i > 0 {
+        writeln!(f)?;
+      }
+
+      write!(
+        f,
+        "{:>6} -> {:<6} {} write(s), first at step {}",
+        site.writer, site.target, site.count, site.first
+      )?;
+
+      if let Some(ref instruction) = site.instruction {
+        write!(f, " ({})", instruction)?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // both instructions overwrite their own opcode
+  const PATCH_SELF: &str = "1,0,0,0,1002,4,3,4,99";
+
+  #[test]
+  fn untracked() {
+    let mut program = Program::from_str(PATCH_SELF).unwrap();
+    program.run(&[]).unwrap();
+
+    assert!(program.self_modifications().is_empty());
+    assert!(program.self_modification_report().is_none());
+  }
+
+  #[test]
+  fn report() {
+    let mut program = Program::from_str(PATCH_SELF).unwrap();
+    program.track_self_modification(Policy::Report);
+    program.run(&[]).unwrap();
+
+    assert_eq!(
+      program.self_modifications(),
+      &[
+        SelfModification {
+          at: 0,
+          writer: 0,
+          target: 0,
+          old: 1,
+          new: 2,
+        },
+        SelfModification {
+          at: 1,
+          writer: 4,
+          target: 4,
+          old: 1002,
+          new: 3006,
+        },
+      ]
+    );
+
+    let report = program.self_modification_report().unwrap();
+    assert_eq!(report.sites.len(), 2);
+    assert_eq!(
+      report.sites[0].instruction.as_deref(),
+      Some("mul [0], [0], [0]")
+    );
+    assert_eq!(report.sites[1].count, 1);
+  }
+
+  #[test]
+  fn error() {
+    let mut program = Program::from_str(PATCH_SELF).unwrap();
+    program.track_self_modification(Policy::Error);
+
+    assert!(program.run(&[]).is_err());
+    assert_eq!(program.self_modifications().len(), 1);
+    assert_eq!(program.read(0).unwrap(), 1);
+  }
+
+  #[test]
+  fn data_writes_are_fine() {
+    // writes only land in the data area after the halt
+    let mut program = Program::from_str("1,9,10,11,2,9,10,12,99,3,4,0,0").unwrap();
+    program.track_self_modification(Policy::Error);
+    program.run(&[]).unwrap();
+
+    assert!(program.self_modification_report().unwrap().is_empty());
+    assert_eq!(program.read(11).unwrap(), 7);
+    assert_eq!(program.read(12).unwrap(), 12);
+  }
+}
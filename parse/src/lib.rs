@@ -0,0 +1,283 @@
+//! Parsing helpers for puzzle inputs, reporting errors with their line and column.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// A parse error, located in the input; lines and columns start at 1.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Error {
+  pub line: usize,
+  pub column: usize,
+  pub message: String,
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "line {}, column {}: {}",
+      self.line, self.column, self.message
+    )
+  }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for String {
+  fn from(e: Error) -> Self {
+    e.to_string()
+  }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// A non-blank line of the input.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Line<'a> {
+  number: usize,
+  raw: &'a str,
+}
+
+impl<'a> Line<'a> {
+  pub fn new(number: usize, raw: &'a str) -> Self {
+    Line { number, raw }
+  }
+
+  /// Line number, starting at 1.
+  pub fn number(&self) -> usize {
+    self.number
+  }
+
+  /// Content of the line, without surrounding whitespace.
+  pub fn text(&self) -> &'a str {
+    self.raw.trim()
+  }
+
+  /// Column at which `s` starts, if it’s a slice of the line; the line start otherwise.
+  pub fn column(&self, s: &str) -> usize {
+    let start = self.raw.as_ptr() as usize;
+    let at = s.as_ptr() as usize;
+
+    if at >= start && at <= start + self.raw.len() {
+      self.raw[..at - start].chars().count() + 1
+    } else {
+      1
+    }
+  }
+
+  /// Error located at `s`, a slice of the line.
+  pub fn error<M>(&self, at: &str, message: M) -> Error
+  where
+    M: Into<String>,
+  {
+    Error {
+      line: self.number,
+      column: self.column(at),
+      message: message.into(),
+    }
+  }
+
+  /// Parse `s`, a slice of the line, ignoring surrounding whitespace.
+  pub fn parse<T>(&self, s: &str) -> Result<T>
+  where
+    T: FromStr,
+    T::Err: fmt::Display,
+  {
+    let s = s.trim();
+    s.parse()
+      .map_err(|e| self.error(s, format!("cannot parse {:?}: {}", s, e)))
+  }
+
+  /// Split the line on `sep` into two parts, such as `A)B`.
+  pub fn pair(&self, sep: &str) -> Result<(&'a str, &'a str)> {
+    let text = self.text();
+    let i = text
+      .find(sep)
+      .ok_or_else(|| self.error(text, format!("expected {:?} in {:?}", sep, text)))?;
+
+    Ok((&text[..i], &text[i + sep.len()..]))
+  }
+
+  /// Numbers separated by `sep`, such as `1,2,3`.
+  pub fn numbers<T>(&self, sep: char) -> Result<Vec<T>>
+  where
+    T: FromStr,
+    T::Err: fmt::Display,
+  {
+    self.text().split(sep).map(|s| self.parse(s)).collect()
+  }
+
+  /// Comma-separated `key=value` pairs enclosed in delimiters, such as `<x=1, y=-2>`.
+  pub fn key_values<T>(&self, open: char, close: char) -> Result<Vec<(&'a str, T)>>
+  where
+    T: FromStr,
+    T::Err: fmt::Display,
+  {
+    let text = self.text();
+    let inner = text
+      .strip_prefix(open)
+      .ok_or_else(|| self.error(text, format!("expected {:?}", open)))?;
+    let inner = inner
+      .strip_suffix(close)
+      .ok_or_else(|| self.error(&text[text.len()..], format!("expected {:?}", close)))?;
+
+    inner
+      .split(',')
+      .map(|kv| {
+        let kv = kv.trim();
+        let i = kv
+          .find('=')
+          .ok_or_else(|| self.error(kv, format!("expected key=value, found {:?}", kv)))?;
+
+        Ok((kv[..i].trim(), self.parse(&kv[i + 1..])?))
+      })
+      .collect()
+  }
+}
+
+/// Lines of the input, ended by `\n`, `\r\n` or a lone `\r`.
+fn raw_lines(input: &str) -> impl Iterator<Item = &str> {
+  let mut rest = Some(input);
+
+  std::iter::from_fn(move || {
+    let s = rest?;
+
+    match s.find(&['\r', '\n'][..]) {
+      Some(i) => {
+        let end = if s[i..].starts_with("\r\n") { 2 } else { 1 };
+        rest = Some(&s[i + end..]);
+        Some(&s[..i])
+      }
+
+      None => {
+        rest = None;
+        Some(s)
+      }
+    }
+  })
+}
+
+/// Non-blank lines of the input, whatever their endings.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+  raw_lines(input)
+    .enumerate()
+    .filter(|(_, raw)| !raw.trim().is_empty())
+    .map(|(i, raw)| Line::new(i + 1, raw))
+}
+
+/// Parse every non-blank line with `f`.
+pub fn records<'a, T, F>(input: &'a str, f: F) -> Result<Vec<T>>
+where
+  F: FnMut(Line<'a>) -> Result<T>,
+{
+  lines(input).map(f).collect()
+}
+
+/// One number per line.
+pub fn numbers<T>(input: &str) -> Result<Vec<T>>
+where
+  T: FromStr,
+  T::Err: fmt::Display,
+{
+  records(input, |line| line.parse(line.text()))
+}
+
+/// Cells of a rectangular grid, row by row, along with its width and height.
+pub fn grid<T, F>(input: &str, mut cell: F) -> Result<(usize, usize, Vec<T>)>
+where
+  F: FnMut(char) -> Option<T>,
+{
+  let mut cells = Vec::new();
+  let mut width = None;
+  let mut height = 0;
+
+  for line in lines(input) {
+    let text = line.text();
+
+    for (i, c) in text.char_indices() {
+      cells.push(cell(c).ok_or_else(|| line.error(&text[i..], format!("unexpected {:?}", c)))?);
+    }
+
+    let len = text.chars().count();
+    match width {
+      None => width = Some(len),
+      Some(width) if width != len => {
+        return Err(line.error(text, format!("expected {} cells, found {}", width, len)))
+      }
+      _ => (),
+    }
+
+    height += 1;
+  }
+
+  Ok((width.unwrap_or(0), height, cells))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn numbers_per_line() {
+    assert_eq!(numbers::<i32>("1\n\n  -2 \n3\n"), Ok(vec![1, -2, 3]));
+
+    let e = numbers::<i32>("1\n  x2\n").unwrap_err();
+    assert_eq!((e.line, e.column), (2, 3));
+    assert_eq!(
+      e.to_string(),
+      "line 2, column 3: cannot parse \"x2\": invalid digit found in string"
+    );
+  }
+
+  #[test]
+  fn line_endings() {
+    let numbers: Vec<_> = lines("1\r\n2\r3\n\r\n4")
+      .map(|line| (line.number(), line.text()))
+      .collect();
+    assert_eq!(numbers, vec![(1, "1"), (2, "2"), (3, "3"), (5, "4")]);
+  }
+
+  #[test]
+  fn delimited() {
+    let line = Line::new(4, " COM)B ");
+    assert_eq!(line.pair(")"), Ok(("COM", "B")));
+    assert_eq!(line.pair("-").unwrap_err().column, 2);
+
+    let line = Line::new(1, "1,2,a");
+    assert_eq!(line.numbers::<u8>(',').unwrap_err().column, 5);
+    assert_eq!(Line::new(1, "1,2").numbers::<u8>(','), Ok(vec![1, 2]));
+  }
+
+  #[test]
+  fn key_values() {
+    let line = Line::new(1, "<x=-1, y=0, z=2>");
+    assert_eq!(
+      line.key_values('<', '>'),
+      Ok(vec![("x", -1), ("y", 0), ("z", 2)])
+    );
+
+    let line = Line::new(1, "<x=-1, y:0>");
+    assert_eq!(line.key_values::<i64>('<', '>').unwrap_err().column, 8);
+
+    let line = Line::new(1, "<x=-1");
+    assert_eq!(line.key_values::<i64>('<', '>').unwrap_err().column, 6);
+  }
+
+  #[test]
+  fn grids() {
+    let cell = |c| match c {
+      '.' => Some(false),
+      '#' => Some(true),
+      _ => None,
+    };
+
+    assert_eq!(
+      grid(".#\n#.\n", cell),
+      Ok((2, 2, vec![false, true, true, false]))
+    );
+
+    let e = grid(".#\n#?\n", cell).unwrap_err();
+    assert_eq!((e.line, e.column), (2, 2));
+    assert_eq!(grid(".#\n#\n", cell).unwrap_err().line, 2);
+  }
+}
@@ -0,0 +1,231 @@
+//! Common interface of all daily solutions.
+
+use std::convert::TryFrom;
+use std::env;
+use std::ffi::OsString;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Answer to a part of a puzzle.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Answer {
+  Number(i64),
+  /// A positive number too large for `Number`.
+  Large(u128),
+  Text(String),
+  /// A picture the answer must be read from, one line per row.
+  Image(String),
+  /// The solution didn’t find any answer.
+  NotFound,
+}
+
+impl Answer {
+  /// Whether the answer needs several lines to be displayed.
+  pub fn is_multiline(&self) -> bool {
+    match self {
+      Answer::Text(text) | Answer::Image(text) => text.trim_end().contains('\n'),
+      _ => false,
+    }
+  }
+}
+
+impl fmt::Display for Answer {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Answer::Number(n) => write!(f, "{}", n),
+      Answer::Large(n) => write!(f, "{}", n),
+      Answer::Text(text) | Answer::Image(text) => f.write_str(text.trim_end()),
+      Answer::NotFound => f.write_str("not found"),
+    }
+  }
+}
+
+macro_rules! impl_answer_from_number {
+  ($($t:ty),*) => {
+    $(
+      impl From<$t> for Answer {
+        fn from(n: $t) -> Self {
+          Answer::Number(n.into())
+        }
+      }
+    )*
+  };
+}
+
+impl_answer_from_number!(i32, i64, u32);
+
+/// Unsigned numbers, which only are `Large` when they don’t fit in a `Number`.
+macro_rules! impl_answer_from_unsigned {
+  ($($t:ty),*) => {
+    $(
+      impl From<$t> for Answer {
+        fn from(n: $t) -> Self {
+          match i64::try_from(n) {
+            Ok(n) => Answer::Number(n),
+            Err(_) => Answer::Large(n as u128),
+          }
+        }
+      }
+    )*
+  };
+}
+
+impl_answer_from_unsigned!(u64, usize, u128);
+
+impl From<String> for Answer {
+  fn from(text: String) -> Self {
+    Answer::Text(text)
+  }
+}
+
+impl<T> From<Option<T>> for Answer
+where
+  T: Into<Answer>,
+{
+  fn from(answer: Option<T>) -> Self {
+    answer.map_or(Answer::NotFound, Into::into)
+  }
+}
+
+/// A day of Advent of Code, answering both parts of its puzzle.
+pub trait Solution {
+  /// Day of the puzzle, starting at 1.
+  fn day(&self) -> u8;
+
+  fn part_1(&self, input: &str) -> Result<Answer, String>;
+
+  fn part_2(&self, input: &str) -> Result<Answer, String>;
+
+  /// Answer the given part (1 or 2).
+  fn part(&self, part: u8, input: &str) -> Result<Answer, String> {
+    match part {
+      1 => self.part_1(input),
+      2 => self.part_2(input),
+      _ => Err(format!("no such part: {}", part)),
+    }
+  }
+}
+
+/// Where to read a puzzle input from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InputSource {
+  /// The `input.txt` file of the day’s crate.
+  Default,
+  /// A file.
+  Path(PathBuf),
+  /// The standard input.
+  Stdin,
+}
+
+impl InputSource {
+  /// Interpret a command-line argument: `-` is the standard input, anything else a path.
+  pub fn from_arg(arg: &str) -> Self {
+    if arg == "-" {
+      InputSource::Stdin
+    } else {
+      InputSource::Path(arg.into())
+    }
+  }
+
+  /// Read the whole input of the given day.
+  pub fn read(&self, day: u8) -> Result<String, String> {
+    match self {
+      InputSource::Default => read_file(&default_input_path(day)),
+
+      InputSource::Path(path) => read_file(path),
+
+      InputSource::Stdin => {
+        let mut input = String::new();
+        io::stdin()
+          .read_to_string(&mut input)
+          .map_err(|e| format!("cannot read input from stdin: {}", e))?;
+        Ok(input)
+      }
+    }
+  }
+}
+
+/// Root of the workspace: `AOC_ROOT` if set, or else the closest directory with a workspace
+/// manifest, starting from the current directory, or else the current directory itself.
+pub fn workspace_root() -> PathBuf {
+  let current = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+  find_root(&current, env::var_os("AOC_ROOT"))
+}
+
+fn find_root(current: &Path, root: Option<OsString>) -> PathBuf {
+  let root = match root.filter(|root| !root.is_empty()) {
+    Some(root) => current.join(root),
+    None => current
+      .ancestors()
+      .find(|dir| is_workspace(dir))
+      .unwrap_or(current)
+      .to_owned(),
+  };
+
+  // resolve `..` and symbolic links, so that displayed paths are readable
+  root.canonicalize().unwrap_or(root)
+}
+
+fn is_workspace(dir: &Path) -> bool {
+  match fs::read_to_string(dir.join("Cargo.toml")) {
+    Ok(manifest) => manifest.lines().any(|line| line.trim() == "[workspace]"),
+    Err(_) => false,
+  }
+}
+
+/// Default location of the input of a day, `dayNN/input.txt` at the root of the workspace.
+pub fn default_input_path(day: u8) -> PathBuf {
+  workspace_root()
+    .join(format!("day{:02}", day))
+    .join("input.txt")
+}
+
+fn read_file(path: &Path) -> Result<String, String> {
+  fs::read_to_string(path).map_err(|e| format!("cannot read input {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn answers() {
+    assert_eq!(Answer::from(42usize), Answer::Number(42));
+    assert_eq!(Answer::from(u64::MAX), Answer::Large(u64::MAX.into()));
+    assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+    assert_eq!(Answer::from(Some(-3)), Answer::Number(-3));
+    assert_eq!(Answer::from(None::<u32>), Answer::NotFound);
+
+    assert_eq!(Answer::Number(-3).to_string(), "-3");
+    assert_eq!(Answer::NotFound.to_string(), "not found");
+    assert_eq!(Answer::Image("#.\n.#\n".to_owned()).to_string(), "#.\n.#");
+
+    assert!(Answer::Image("#.\n.#\n".to_owned()).is_multiline());
+    assert!(!Answer::Text("abc\n".to_owned()).is_multiline());
+    assert!(!Answer::Number(1).is_multiline());
+  }
+
+  #[test]
+  fn roots() {
+    let tmp = env::temp_dir().join(format!("aoc-root-{}", std::process::id()));
+    let day = tmp.join("day01").join("src");
+    fs::create_dir_all(&day).unwrap();
+    fs::write(
+      tmp.join("Cargo.toml"),
+      "[workspace]\nmembers = [\"day01\"]\n",
+    )
+    .unwrap();
+    fs::write(tmp.join("day01").join("Cargo.toml"), "[package]\n").unwrap();
+
+    let root = tmp.canonicalize().unwrap();
+    assert_eq!(find_root(&day, None), root);
+    assert_eq!(find_root(&root, None), root);
+    assert_eq!(find_root(&day, Some("".into())), root);
+    assert_eq!(find_root(&day, Some("..".into())), root.join("day01"));
+    assert_eq!(find_root(&day, Some(tmp.clone().into())), root);
+
+    fs::remove_dir_all(&tmp).unwrap();
+  }
+}